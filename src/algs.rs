@@ -1,9 +1,9 @@
-use crate::consts::PRIMES as primes;
+use crate::native;
+use crate::sieve;
 use num::bigint::BigInt;
 use num::Integer;
 use num::ToPrimitive;
-use num_bigint::RandBigInt;
-use num_traits::One;
+use num_traits::{One, Zero};
 
 pub fn mod_pow(b: BigInt, e: u64, m: BigInt) -> BigInt {
     let mut s: BigInt = BigInt::from(1);
@@ -21,14 +21,12 @@ pub fn mod_pow(b: BigInt, e: u64, m: BigInt) -> BigInt {
     s
 }
 
-pub fn inv(a: BigInt, p: BigInt) -> BigInt {
-    let res = a.extended_gcd(&p);
-    res.x % p
-}
-
-pub fn is_quadratic(a: BigInt, p: BigInt) -> bool {
-    let e: BigInt = (p.clone() - 1u64) >> 1u64;
-    a.modpow(&e, &p) == BigInt::one()
+pub fn prime_power(p: u64, bound: u64) -> u64 {
+    let mut q = p;
+    while q <= bound / p {
+        q *= p;
+    }
+    q
 }
 
 pub fn is_square(n: &BigInt) -> bool {
@@ -38,42 +36,279 @@ pub fn is_square(n: &BigInt) -> bool {
     x.clone() * x.clone() == n
 }
 
-pub fn miller_rabin(n: &BigInt) -> bool {
+pub fn perfect_power(n: &BigInt) -> Option<(BigInt, u32)> {
+    if *n <= BigInt::one() {
+        return None;
+    }
+    let bits = n.bits();
+    for &k in sieve::primes(bits).iter() {
+        let r = n.nth_root(k as u32);
+        if r.pow(k as u32) == *n {
+            return Some((r, k as u32));
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Primality {
+    // Proven: n is below 2^64, where Miller-Rabin to fixed bases is exact.
+    Prime,
+    // Passed BPSW, to which no counterexample is known.
+    ProbablePrime,
+    Composite,
+}
+
+pub fn primality(n: &BigInt) -> Primality {
+    if let Some(n) = n.to_u64() {
+        return if native::is_prime(n) {
+            Primality::Prime
+        } else {
+            Primality::Composite
+        };
+    }
+    if bpsw(n) {
+        Primality::ProbablePrime
+    } else {
+        Primality::Composite
+    }
+}
+
+pub fn is_prime(n: &BigInt) -> bool {
+    primality(n) != Primality::Composite
+}
+
+// The smallest prime above n.
+pub fn next_prime(n: &BigInt) -> BigInt {
+    let two = BigInt::from(2u32);
+    if *n < two {
+        return two;
+    }
+    let mut p: BigInt = n + 1u32;
+    if p.is_even() {
+        p += 1u32;
+    }
+    while !is_prime(&p) {
+        p += 2u32;
+    }
+    p
+}
+
+// The largest prime below n, if n > 2.
+pub fn prev_prime(n: &BigInt) -> Option<BigInt> {
+    if *n <= BigInt::from(3u32) {
+        return (*n == BigInt::from(3u32)).then(|| BigInt::from(2u32));
+    }
+    let mut p: BigInt = n - 1u32;
+    if p.is_even() {
+        p -= 1u32;
+    }
+    while !is_prime(&p) {
+        p -= 2u32;
+    }
+    Some(p)
+}
+
+// Baillie-PSW: a strong Fermat test to base 2 followed by a strong Lucas test
+// with Selfridge's parameters.
+fn bpsw(n: &BigInt) -> bool {
     if *n <= BigInt::one() {
         return false;
     }
-    let mut m: BigInt = n - 1u64;
-    let mut k: u64 = 0;
-    while m.is_even() {
-        k += 1u64;
-        m >>= 1u64;
+    for &p in sieve::primes(1000).iter() {
+        if (n % p).is_zero() {
+            return *n == BigInt::from(p);
+        }
+    }
+    strong_fermat(n) && strong_lucas(n)
+}
+
+fn strong_fermat(n: &BigInt) -> bool {
+    let n1: BigInt = n - 1u32;
+    let s = n1.trailing_zeros().unwrap();
+    let mut x = BigInt::from(2u32).modpow(&(&n1 >> s), n);
+    if x.is_one() || x == n1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = &x * &x % n;
+        if x == n1 {
+            return true;
+        }
     }
+    false
+}
 
-    let threshold = 2;
-    for _ in 0..threshold {
-        if !internal_test(n, &m, &k) {
-            return false;
+// Jacobi symbol (a / n) for odd positive n.
+pub fn jacobi(a: &BigInt, n: &BigInt) -> i32 {
+    let mut a = a.mod_floor(n);
+    let mut n = n.clone();
+    let mut t = 1;
+    while !a.is_zero() {
+        let z = a.trailing_zeros().unwrap();
+        a >>= z;
+        let n8 = (&n % 8u32).to_u32().unwrap();
+        if z % 2 == 1 && (n8 == 3 || n8 == 5) {
+            t = -t;
         }
+        std::mem::swap(&mut a, &mut n);
+        if (&a % 4u32) == BigInt::from(3u32) && (&n % 4u32) == BigInt::from(3u32) {
+            t = -t;
+        }
+        a = a.mod_floor(&n);
+    }
+    if n.is_one() {
+        t
+    } else {
+        0
     }
-    true
 }
-fn internal_test(n: &BigInt, m: &BigInt, k: &u64) -> bool {
-    let mut rng = rand::thread_rng();
-    let low = BigInt::from(2);
-    let high = n.clone();
-    let a = rng.gen_bigint_range(&low, &high);
 
-    let mut b = a.modpow(&m, &n);
-    for _ in 0..*k {
-        if b.clone() % n.clone() == n.clone() - 1u64 {
+// Strong Lucas probable prime test for odd n > 1 coprime to small primes:
+// with D the first of 5, -7, 9, -11, ... having (D / n) = -1, P = 1 and
+// Q = (1 - D) / 4, and n + 1 = d 2^s, either U_d = 0 or V_(d 2^r) = 0 mod n
+// for some r < s.
+fn strong_lucas(n: &BigInt) -> bool {
+    if is_square(n) {
+        // No D would ever be found.
+        return false;
+    }
+    let mut d = 5i64;
+    while jacobi(&BigInt::from(d), n) != -1 {
+        d = if d > 0 { -d - 2 } else { -d + 2 };
+    }
+    let big_d = BigInt::from(d).mod_floor(n);
+    let q = BigInt::from((1 - d) / 4).mod_floor(n);
+    let half = |x: BigInt| if x.is_odd() { (x + n) >> 1 } else { x >> 1 };
+
+    let n1: BigInt = n + 1u32;
+    let s = n1.trailing_zeros().unwrap();
+    let k = &n1 >> s;
+    // U_1 = 1, V_1 = P = 1, Q^1.
+    let (mut u, mut v, mut qk) = (BigInt::one(), BigInt::one(), q.clone());
+    for i in (0..k.bits() - 1).rev() {
+        u = &u * &v % n;
+        v = (&v * &v - &qk * 2u32).mod_floor(n);
+        qk = &qk * &qk % n;
+        if k.bit(i) {
+            let u1 = half(&u + &v);
+            v = half(&big_d * &u + &v) % n;
+            u = u1 % n;
+            qk = &qk * &q % n;
+        }
+    }
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = (&v * &v - &qk * 2u32).mod_floor(n);
+        if v.is_zero() {
             return true;
         }
-        b = b.modpow(&BigInt::from(2), n);
+        qk = &qk * &qk % n;
     }
     false
 }
 
-pub fn is_prime(n: &BigInt) -> bool {
-    let n_u64 = n.to_u64().unwrap();
-    primes.binary_search(&n_u64).is_ok() || miller_rabin(n)
+pub fn mod_pow_u64(b: u64, e: u64, m: u64) -> u64 {
+    let (mut s, mut t, mut e) = (1 % m, b % m, e);
+    while e > 0 {
+        if e & 1 == 1 {
+            s = (s as u128 * t as u128 % m as u128) as u64;
+        }
+        t = (t as u128 * t as u128 % m as u128) as u64;
+        e >>= 1;
+    }
+    s
+}
+
+// Tonelli-Shanks: a square root of a modulo an odd prime p, if there is one.
+pub fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if p == 2 || a == 0 {
+        return Some(a);
+    }
+    if mod_pow_u64(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    let mul = |x: u64, y: u64| (x as u128 * y as u128 % p as u128) as u64;
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p).find(|&z| mod_pow_u64(z, (p - 1) / 2, p) == p - 1)?;
+
+    let mut m = s;
+    let mut c = mod_pow_u64(z, q, p);
+    let mut t = mod_pow_u64(a, q, p);
+    let mut r = mod_pow_u64(a, q.div_ceil(2), p);
+    while t != 1 {
+        let mut i = 1;
+        let mut t2 = mul(t, t);
+        while t2 != 1 {
+            t2 = mul(t2, t2);
+            i += 1;
+        }
+        let b = mod_pow_u64(c, 1 << (m - i - 1), p);
+        m = i;
+        c = mul(b, b);
+        t = mul(t, c);
+        r = mul(r, b);
+    }
+    Some(r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_bpsw() {
+        // Strong pseudoprimes to base 2, and strong Lucas pseudoprimes.
+        for n in [2047u32, 3277, 4033, 4681, 8321] {
+            assert!(!strong_lucas(&BigInt::from(n)));
+        }
+        for n in [5459u32, 5777, 10877, 16109, 18971] {
+            assert!(!strong_fermat(&BigInt::from(n)));
+        }
+        assert!(!bpsw(&BigInt::from(1u32)));
+        assert!(bpsw(&BigInt::from(2u32)));
+        assert!(bpsw(&BigInt::from(1000003u32)));
+
+        let m127 = bi!("170141183460469231731687303715884105727", 10);
+        assert_eq!(primality(&m127), Primality::ProbablePrime);
+        assert_eq!(primality(&(&m127 * &m127)), Primality::Composite);
+        // A Carmichael number (6k + 1)(12k + 1)(18k + 1), and 2^128 + 1.
+        assert!(!is_prime(&bi!("1296056805229926801774649", 10)));
+        assert!(!is_prime(&bi!("340282366920938463463374607431768211457", 10)));
+        assert_eq!(primality(&BigInt::from(u64::MAX - 58)), Primality::Prime);
+    }
+
+    #[test]
+    fn test_next_prime() {
+        let p = |n: i64| BigInt::from(n);
+        assert_eq!(next_prime(&p(-5)), p(2));
+        assert_eq!(next_prime(&p(2)), p(3));
+        assert_eq!(next_prime(&p(3)), p(5));
+        assert_eq!(next_prime(&p(24)), p(29));
+        assert_eq!(
+            next_prime(&bi!("340282366920938463463374607431768211456", 10)),
+            bi!("340282366920938463463374607431768211507", 10)
+        );
+        assert_eq!(prev_prime(&p(2)), None);
+        assert_eq!(prev_prime(&p(3)), Some(p(2)));
+        assert_eq!(prev_prime(&p(4)), Some(p(3)));
+        assert_eq!(prev_prime(&p(29)), Some(p(23)));
+        assert_eq!(
+            prev_prime(&bi!("18446744073709551616", 10)),
+            Some(BigInt::from(u64::MAX - 58))
+        );
+    }
+
+    #[test]
+    fn test_jacobi() {
+        assert_eq!(jacobi(&BigInt::from(2), &BigInt::from(7)), 1);
+        assert_eq!(jacobi(&BigInt::from(-1), &BigInt::from(7)), -1);
+        assert_eq!(jacobi(&BigInt::from(5), &BigInt::from(9)), 1);
+        assert_eq!(jacobi(&BigInt::from(6), &BigInt::from(9)), 0);
+    }
 }
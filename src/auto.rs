@@ -0,0 +1,112 @@
+use crate::driver;
+use crate::ecm;
+use crate::fermat;
+use crate::native;
+use crate::pm1;
+use crate::qs;
+use crate::rho;
+use crate::sieve;
+use crate::squfof;
+use crate::traits::{Factorizer, Factors, Splitter, Status};
+use num::bigint::BigInt;
+use num::{One, Zero};
+
+const TRIAL_BOUND: u64 = 10_000;
+const FERMAT_STEPS: u64 = 10_000;
+const RHO_ITERATIONS: u64 = 1 << 16;
+// (digits, B1, curves) for each ECM round, aimed at factors of that many
+// digits. A round only runs while its factors are below 2/9 of the digits of
+// n; beyond that the quadratic sieve is the faster way to split n.
+const ECM_SCHEDULE: [(usize, u64, usize); 3] =
+    [(15, 2_000, 25), (20, 11_000, 90), (25, 50_000, 300)];
+
+pub struct Auto {
+    n: BigInt,
+    jobs: usize,
+}
+
+impl Auto {
+    pub fn new(n: BigInt) -> Self {
+        Self { n, jobs: 1 }
+    }
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+}
+
+impl Factorizer for Auto {
+    fn factorize(&self) -> Option<Factors> {
+        let n = &self.n;
+        if *n < BigInt::one() {
+            return None;
+        }
+        if n.bits() <= 64 {
+            return native::factors(n);
+        }
+        let mut n = n.clone();
+        let mut factors = Factors::new(None);
+        for &p in sieve::primes(TRIAL_BOUND).iter() {
+            while (&n % p).is_zero() {
+                factors.insert(BigInt::from(p), 1, Status::Prime);
+                n /= p;
+            }
+        }
+        factors.merge(driver::factorize(&n, self)?);
+        Some(factors)
+    }
+}
+
+impl Splitter for Auto {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        split(n, self.jobs)
+    }
+    fn fallback(&self) -> bool {
+        true
+    }
+}
+
+// Runs the splitting stages from cheapest to most expensive and returns the
+// first nontrivial divisor found.
+fn split(n: &BigInt, jobs: usize) -> Option<BigInt> {
+    let digits = n.to_string().len();
+    squfof::split(n)
+        .or_else(|| fermat::split(n, FERMAT_STEPS))
+        .or_else(|| rho::split(n, RHO_ITERATIONS, jobs))
+        .or_else(|| pm1::split(n, pm1::DEFAULT_B1, pm1::DEFAULT_B2, jobs))
+        .or_else(|| {
+            ECM_SCHEDULE
+                .iter()
+                .take_while(|&&(d, _, _)| 9 * d <= 2 * digits)
+                .find_map(|&(_, b1, curves)| ecm::split(n, b1, 100 * b1, curves, jobs))
+        })
+        .or_else(|| qs::split(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+    use num::bigint::BigInt;
+
+    #[test]
+    fn test_auto() {
+        let n = bi!("13251537330083289031", 10);
+        let f = Auto::new(n.clone()).factorize().unwrap();
+        assert_eq!(f.n(), n);
+        assert_eq!(f.get_factors_expr(), "31 * 199 * 347 * 6190447136717");
+
+        let n = BigInt::from(3u32).pow(40) * BigInt::from(1000003u32).pow(3);
+        let f = Auto::new(n.clone()).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "3^40 * 1000003^3");
+
+        // Balanced factors of 25 digits are beyond rho, p-1 and the ECM
+        // rounds worth running on 49 digits; the quadratic sieve splits n.
+        let n = bi!("1000000000000000000000007", 10) * bi!("3000000000000000000000007", 10);
+        let f = Auto::new(n).factorize().unwrap();
+        assert_eq!(
+            f.get_factors_expr(),
+            "P?1000000000000000000000007 * P?3000000000000000000000007"
+        );
+    }
+}
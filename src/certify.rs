@@ -0,0 +1,504 @@
+use crate::algs::{is_prime, is_square};
+use crate::ecm;
+use crate::ecpp;
+use crate::elliptic_curve::{affine_mul, EllipticCurve};
+use crate::galois_field::GaloisField;
+use crate::native;
+use crate::pm1;
+use crate::rho;
+use crate::sieve;
+use crate::traits::Factors;
+use num::bigint::BigInt;
+use num::{Integer, One, ToPrimitive, Zero};
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+// Primality certificates: a list of steps, each proving one prime from
+// primes proven by earlier steps, in a text format of one step per line:
+//
+//   <n> small                            n < 2^16, by trial division
+//   <n> pratt <a> <q1> <q2> ...          a has order n - 1 = prod q_i^e_i
+//   <n> pocklington <q1>:<a1> ...        n - 1 is factored far enough
+//   <n> ecpp <a> <b> <m> <q> <x> <y>     (x, y) has order q on a curve
+//
+// Blank lines, lines starting with '#' and the "n = ..." line fr prints above
+// a certificate are ignored, so that `fr --certify` output checks as is.
+
+// Primes below this bound are proven by trial division.
+const SMALL: u64 = 1 << 16;
+
+// Effort spent on the unfactored part of n - 1 before giving up.
+const TRIAL_BOUND: u64 = 10_000;
+const RHO_ITERATIONS: u64 = 1 << 16;
+const ECM_CURVES: usize = 25;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Proof {
+    Small,
+    // Lucas' theorem: a^(n-1) = 1 and a^((n-1)/q) != 1 mod n for every prime
+    // q dividing n - 1, given as the complete list of those q.
+    Pratt { a: BigInt, factors: Vec<BigInt> },
+    // Pocklington with the Brillhart-Lehmer-Selfridge extension: each (q, a)
+    // has a^(n-1) = 1 and gcd(a^((n-1)/q) - 1, n) = 1, so every prime factor
+    // of n is 1 mod F, the part of n - 1 made of the q. That proves n prime
+    // once F^2 > n, or once F^3 > n and the base F digits n = c2 F^2 + c1 F + 1
+    // leave c1^2 - 4 c2 a non-square.
+    Pocklington { witnesses: Vec<(BigInt, BigInt)> },
+    // Goldwasser-Kilian: P = (x, y) on y^2 = x^3 + ax + b has (m / q) P != O
+    // and m P = O, computed without a failed inversion mod n. P then has order
+    // q modulo each prime p dividing n, so q <= (p^(1/2) + 1)^2, and q >
+    // (n^(1/4) + 1)^2 rules out every p below n^(1/2).
+    Ecpp {
+        a: BigInt,
+        b: BigInt,
+        m: BigInt,
+        q: BigInt,
+        x: BigInt,
+        y: BigInt,
+    },
+}
+
+impl Proof {
+    // The primes the proof takes as proven.
+    fn uses(&self) -> Vec<&BigInt> {
+        match self {
+            Proof::Small => Vec::new(),
+            Proof::Pratt { factors, .. } => factors.iter().collect(),
+            Proof::Pocklington { witnesses } => witnesses.iter().map(|(q, _)| q).collect(),
+            Proof::Ecpp { q, .. } => vec![q],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub n: BigInt,
+    pub proof: Proof,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Certificate {
+    steps: Vec<Step>,
+}
+
+impl Certificate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+    pub fn proves(&self, p: &BigInt) -> bool {
+        self.steps.iter().any(|s| s.n == *p)
+    }
+
+    // The primes no other step relies on: those the certificate is for.
+    pub fn targets(&self) -> Vec<&BigInt> {
+        let used: HashSet<&BigInt> = self.steps.iter().flat_map(|s| s.proof.uses()).collect();
+        self.steps
+            .iter()
+            .map(|s| &s.n)
+            .filter(|n| !used.contains(n))
+            .collect()
+    }
+
+    // Adds the steps proving p prime, and those for the primes they rely on.
+    // Returns false, leaving the certificate as it was, if p is composite or
+    // no proof was found.
+    pub fn certify(&mut self, p: &BigInt, jobs: usize) -> bool {
+        if self.proves(p) {
+            return true;
+        }
+        if !is_prime(p) {
+            return false;
+        }
+        let proof = if *p < BigInt::from(SMALL) {
+            Some(Proof::Small)
+        } else if p.bits() <= 64 {
+            self.attempt(|c| c.pratt(p, jobs))
+        } else {
+            // n - 1 by trial division, then ECPP, and only then the costly
+            // attempts at splitting n - 1 further.
+            self.attempt(|c| c.pocklington(p, false, jobs))
+                .or_else(|| self.attempt(|c| c.ecpp(p, jobs)))
+                .or_else(|| self.attempt(|c| c.pocklington(p, true, jobs)))
+        };
+        let Some(proof) = proof else {
+            return false;
+        };
+        self.steps.push(Step {
+            n: p.clone(),
+            proof,
+        });
+        true
+    }
+
+    // One way of proving a prime, dropping the steps it added if it fails.
+    fn attempt(&mut self, f: impl FnOnce(&mut Self) -> Option<Proof>) -> Option<Proof> {
+        let len = self.steps.len();
+        let proof = f(self);
+        if proof.is_none() {
+            self.steps.truncate(len);
+        }
+        proof
+    }
+
+    // n - 1 is below 2^64 and factors completely on machine words.
+    fn pratt(&mut self, n: &BigInt, jobs: usize) -> Option<Proof> {
+        let n1: BigInt = n - 1u32;
+        let factors: Vec<BigInt> = native::factors(&n1)?
+            .get_factors_vector()
+            .iter()
+            .map(|f| f.pe().0)
+            .collect();
+        for q in &factors {
+            if !self.certify(q, jobs) {
+                return None;
+            }
+        }
+        let a = (2u32..)
+            .map(BigInt::from)
+            .find(|a| factors.iter().all(|q| !a.modpow(&(&n1 / q), n).is_one()))?;
+        Some(Proof::Pratt { a, factors })
+    }
+
+    // Factors n - 1 until the proven part F has F^3 > n, by trial division
+    // and, if `hard`, rho, p - 1 and ECM.
+    fn pocklington(&mut self, n: &BigInt, hard: bool, jobs: usize) -> Option<Proof> {
+        let n1: BigInt = n - 1u32;
+        let mut f = BigInt::one();
+        let mut witnesses: Vec<(BigInt, BigInt)> = Vec::new();
+        let mut add = |c: &mut Self, q: BigInt, f: &mut BigInt| -> bool {
+            if witnesses.iter().any(|(p, _)| *p == q) || !c.certify(&q, jobs) {
+                return false;
+            }
+            let mut m = n1.clone();
+            while (&m % &q).is_zero() {
+                m /= &q;
+                *f *= &q;
+            }
+            let a = (2u32..)
+                .map(BigInt::from)
+                .find(|a| !a.modpow(&(&n1 / &q), n).is_one())
+                .unwrap();
+            witnesses.push((q, a));
+            true
+        };
+        let done = |f: &BigInt| f.pow(3) > *n;
+
+        let mut r = n1.clone();
+        for &p in sieve::primes(TRIAL_BOUND).iter() {
+            if (&r % p).is_zero() {
+                while (&r % p).is_zero() {
+                    r /= p;
+                }
+                add(self, BigInt::from(p), &mut f);
+            }
+        }
+        let mut pieces = vec![r];
+        while let Some(m) = pieces.pop() {
+            if done(&f) {
+                break;
+            }
+            if m.is_one() {
+                continue;
+            }
+            if is_prime(&m) {
+                add(self, m, &mut f);
+                continue;
+            }
+            if let Some(d) = hard.then(|| split(&m, jobs)).flatten() {
+                pieces.push(&m / &d);
+                pieces.push(d);
+            }
+        }
+        if !done(&f) {
+            return None;
+        }
+        witnesses.sort();
+        Some(Proof::Pocklington { witnesses })
+    }
+
+    // Atkin-Morain: the first order with a curve whose q can be proven in
+    // turn.
+    fn ecpp(&mut self, n: &BigInt, jobs: usize) -> Option<Proof> {
+        ecpp::orders(n).into_iter().find_map(|order| {
+            let (a, b, x, y) = ecpp::curve(n, order.d, &order.m, &order.q)?;
+            self.certify(&order.q, jobs).then_some(Proof::Ecpp {
+                a,
+                b,
+                m: order.m,
+                q: order.q,
+                x,
+                y,
+            })
+        })
+    }
+
+    // Checks every step in order, each relying only on earlier ones.
+    pub fn verify(&self) -> Result<(), String> {
+        let mut proven: HashSet<&BigInt> = HashSet::new();
+        for step in &self.steps {
+            let n = &step.n;
+            if *n < BigInt::from(2u32) {
+                return Err(format!("{n}: not prime"));
+            }
+            let n1: BigInt = n - 1u32;
+            let unproven = |q: &BigInt| {
+                if proven.contains(q) {
+                    Ok(())
+                } else {
+                    Err(format!("{n}: {q} is not proven prime by an earlier step"))
+                }
+            };
+            match &step.proof {
+                Proof::Small => {
+                    let m = n
+                        .to_u64()
+                        .filter(|&m| m < SMALL)
+                        .ok_or(format!("{n}: too large for trial division"))?;
+                    if (2..).take_while(|d| d * d <= m).any(|d| m % d == 0) {
+                        return Err(format!("{n}: not prime"));
+                    }
+                }
+                Proof::Pratt { a, factors } => {
+                    let mut r = n1.clone();
+                    for q in factors {
+                        unproven(q)?;
+                        while (&r % q).is_zero() {
+                            r /= q;
+                        }
+                        if a.modpow(&(&n1 / q), n).is_one() {
+                            return Err(format!("{n}: {a} is not a witness for {q}"));
+                        }
+                    }
+                    if !r.is_one() {
+                        return Err(format!("{n}: n - 1 is not completely factored"));
+                    }
+                    if !a.modpow(&n1, n).is_one() {
+                        return Err(format!("{n}: {a}^(n - 1) != 1"));
+                    }
+                }
+                Proof::Pocklington { witnesses } => {
+                    let mut r = n1.clone();
+                    for (q, a) in witnesses {
+                        unproven(q)?;
+                        if !(&n1 % q).is_zero() {
+                            return Err(format!("{n}: {q} does not divide n - 1"));
+                        }
+                        while (&r % q).is_zero() {
+                            r /= q;
+                        }
+                        let x = a.modpow(&(&n1 / q), n);
+                        if !a.modpow(&n1, n).is_one() || !(x - 1u32).gcd(n).is_one() {
+                            return Err(format!("{n}: {a} is not a witness for {q}"));
+                        }
+                    }
+                    let f = &n1 / &r;
+                    if &f * &f <= *n {
+                        if f.pow(3) <= *n {
+                            return Err(format!("{n}: factored part of n - 1 too small"));
+                        }
+                        let (c2, c1) = (&n1 / &f).div_rem(&f);
+                        let d: BigInt = &c1 * &c1 - c2 * 4u32;
+                        if d >= BigInt::zero() && is_square(&d) {
+                            return Err(format!("{n}: c1^2 - 4 c2 is a square"));
+                        }
+                    }
+                }
+                Proof::Ecpp { a, b, m, q, x, y } => {
+                    unproven(q)?;
+                    if !n.gcd(&BigInt::from(6u32)).is_one() {
+                        return Err(format!("{n}: divisible by 2 or 3"));
+                    }
+                    if !(m % q).is_zero() || !ecpp::large_enough(q, n) {
+                        return Err(format!("{n}: {q} is not a large factor of {m}"));
+                    }
+                    let disc: BigInt = a.pow(3) * 4u32 + b.pow(2) * 27u32;
+                    if !disc.gcd(n).is_one() {
+                        return Err(format!("{n}: singular curve"));
+                    }
+                    let e = EllipticCurve::new(&GaloisField::GaloisField(n), a, b);
+                    if !e.contains(x, y) {
+                        return Err(format!("{n}: ({x}, {y}) is not on the curve"));
+                    }
+                    let u = affine_mul(&(m / q), &e.new_point(x, y))
+                        .filter(|u| *u != e.o())
+                        .ok_or(format!("{n}: (m / q) P is not a point of order q"))?;
+                    if affine_mul(q, &u) != Some(e.o()) {
+                        return Err(format!("{n}: m P != O"));
+                    }
+                }
+            }
+            proven.insert(n);
+        }
+        Ok(())
+    }
+}
+
+// A cheap attempt at a divisor of the cofactor of n - 1.
+fn split(n: &BigInt, jobs: usize) -> Option<BigInt> {
+    rho::split(n, RHO_ITERATIONS, jobs)
+        .or_else(|| pm1::split(n, pm1::DEFAULT_B1, pm1::DEFAULT_B2, jobs))
+        .or_else(|| ecm::split(n, ecm::DEFAULT_B1, ecm::DEFAULT_B2, ECM_CURVES, jobs))
+}
+
+// A certificate for every prime factor, and the factors left without one.
+pub fn factors(factors: &Factors, jobs: usize) -> (Certificate, Vec<BigInt>) {
+    let mut certificate = Certificate::new();
+    let failed = factors
+        .get_factors_vector()
+        .iter()
+        .map(|f| f.pe().0)
+        .filter(|p| is_prime(p) && !certificate.certify(p, jobs))
+        .collect();
+    (certificate, failed)
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.proof {
+            Proof::Small => write!(f, "{} small", self.n),
+            Proof::Pratt { a, factors } => {
+                write!(f, "{} pratt {a}", self.n)?;
+                factors.iter().try_for_each(|q| write!(f, " {q}"))
+            }
+            Proof::Pocklington { witnesses } => {
+                write!(f, "{} pocklington", self.n)?;
+                witnesses
+                    .iter()
+                    .try_for_each(|(q, a)| write!(f, " {q}:{a}"))
+            }
+            Proof::Ecpp { a, b, m, q, x, y } => {
+                write!(f, "{} ecpp {a} {b} {m} {q} {x} {y}", self.n)
+            }
+        }
+    }
+}
+
+impl Display for Certificate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.steps.iter().try_for_each(|s| writeln!(f, "{s}"))
+    }
+}
+
+impl FromStr for Step {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |t: &str| {
+            BigInt::parse_bytes(t.as_bytes(), 10).ok_or(format!("bad number '{t}' in '{s}'"))
+        };
+        let mut tokens = s.split_whitespace();
+        let n = number(tokens.next().unwrap_or_default())?;
+        let proof = match tokens.next() {
+            Some("small") => Proof::Small,
+            Some("pratt") => Proof::Pratt {
+                a: number(tokens.next().unwrap_or_default())?,
+                factors: tokens.map(number).collect::<Result<_, _>>()?,
+            },
+            Some("pocklington") => Proof::Pocklington {
+                witnesses: tokens
+                    .map(|t| {
+                        let (q, a) = t.split_once(':').ok_or(format!("bad witness '{t}'"))?;
+                        Ok((number(q)?, number(a)?))
+                    })
+                    .collect::<Result<_, String>>()?,
+            },
+            Some("ecpp") => {
+                let mut next = || number(tokens.next().unwrap_or_default());
+                Proof::Ecpp {
+                    a: next()?,
+                    b: next()?,
+                    m: next()?,
+                    q: next()?,
+                    x: next()?,
+                    y: next()?,
+                }
+            }
+            _ => return Err(format!("unknown proof in '{s}'")),
+        };
+        Ok(Self { n, proof })
+    }
+}
+
+impl FromStr for Certificate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Blank lines, comments and the `name = value` lines of fr's output,
+        // such as `n = ...` and `sigma = ...`, are skipped.
+        let steps = s
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.contains('='))
+            .map(Step::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { steps })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_certify() {
+        // 2^89 - 1 needs Pocklington; the primes of its n - 1 below 2^64 need
+        // Pratt.
+        let p = bi!("618970019642690137449562111", 10);
+        let mut c = Certificate::new();
+        assert!(c.certify(&p, 1));
+        assert!(c.certify(&BigInt::from(u64::MAX - 58), 1));
+        assert!(!c.certify(&(&p * 3u32), 1));
+        assert!(c.verify().is_ok());
+        assert!(matches!(
+            c.steps().last().unwrap().proof,
+            Proof::Pratt { .. }
+        ));
+        assert_eq!(c.targets(), [&p, &BigInt::from(u64::MAX - 58)]);
+        let s = c.to_string();
+        assert_eq!(s.parse::<Certificate>(), Ok(c));
+
+        // 2^127 - 1: n - 1 = 2 * 3^3 * 7^2 * 19 * 43 * 73 * 127 * 337 * ... .
+        let p = bi!("170141183460469231731687303715884105727", 10);
+        let mut c = Certificate::new();
+        assert!(c.certify(&p, 1));
+        assert!(c.verify().is_ok());
+        assert_eq!(c.steps().last().unwrap().n, p);
+
+        // ECPP, whatever shape n - 1 has.
+        let p = bi!("100000000000000000000000000000000000000000000000151", 10);
+        let mut c = Certificate::new();
+        let proof = c.ecpp(&p, 1).unwrap();
+        c.steps.push(Step { n: p, proof });
+        assert!(c.verify().is_ok());
+        let s = c.to_string();
+        assert_eq!(s.parse::<Certificate>(), Ok(c.clone()));
+        let Some(Step {
+            proof: Proof::Ecpp { x, .. },
+            ..
+        }) = c.steps.last_mut()
+        else {
+            panic!("not an ECPP step");
+        };
+        *x += 1u32;
+        assert!(c.verify().is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let check = |s: &str| s.parse::<Certificate>().unwrap().verify();
+        assert!(check("# ok\n2 small\n3 small\n\n7 pratt 3 2 3\n").is_ok());
+        assert!(check("7 pratt 3 2 3").is_err());
+        assert!(check("2 small\n3 small\n7 pratt 2 2 3").is_err());
+        assert!(check("65537 small").is_err());
+        assert!(check("2 small\n3 small\n25 pratt 2 2 3").is_err());
+        // 91 = 7 * 13 has no witnesses.
+        assert!(check("2 small\n3 small\n91 pocklington 2:2 3:2").is_err());
+        assert!("7 lucas 3".parse::<Certificate>().is_err());
+        assert!("7 pocklington 3".parse::<Certificate>().is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::algs::{perfect_power, sqrt_mod};
+use crate::driver;
+use crate::linalg::{Relation, Relations};
+use crate::qs::multiplier;
+use crate::rho;
+use crate::sieve::Sieve;
+use crate::traits::{Factorizer, Factors, Splitter};
+use num::bigint::BigInt;
+use num::{Integer, One, ToPrimitive, Zero};
+
+// Partial relations are kept when the cofactor is below LARGE_PRIME times the
+// largest factor base prime.
+const LARGE_PRIME: u64 = 64;
+
+// Relations collected beyond the number of matrix columns.
+const EXTRA_RELATIONS: usize = 64;
+
+// (digits, factor base size)
+const PARAMS: [(usize, usize); 7] = [
+    (20, 80),
+    (25, 120),
+    (30, 180),
+    (35, 280),
+    (40, 400),
+    (45, 700),
+    (50, 1200),
+];
+
+pub struct Cfrac {
+    n: BigInt,
+}
+
+impl Cfrac {
+    pub fn new(n: BigInt) -> Self {
+        Self { n }
+    }
+}
+
+impl Factorizer for Cfrac {
+    fn factorize(&self) -> Option<Factors> {
+        driver::factorize(&self.n, self)
+    }
+}
+
+impl Splitter for Cfrac {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        split(n)
+    }
+}
+
+// Morrison-Brillhart continued fraction method. The convergents A_i / B_i of
+// sqrt(kn) satisfy A_i^2 - kn B_i^2 = (-1)^(i+1) Q_(i+1) with Q_(i+1) below
+// 2 sqrt(kn), so A_i^2 = (-1)^(i+1) Q_(i+1) mod n. Smooth Q are combined into
+// a congruence of squares by linear algebra over GF(2).
+pub fn split(n: &BigInt) -> Option<BigInt> {
+    if n.is_even() {
+        return Some(BigInt::from(2));
+    }
+    if let Some((r, _)) = perfect_power(n) {
+        return Some(r);
+    }
+    // Numbers this small are not worth collecting relations for.
+    if n.bits() < 64 {
+        return rho::split(n, u64::MAX, 1);
+    }
+
+    let k = multiplier(n);
+    let kn = n * k;
+    let digits = n.to_string().len();
+    let &(_, fb_size) = PARAMS
+        .iter()
+        .find(|&&(d, _)| d >= digits)
+        .unwrap_or(&PARAMS[PARAMS.len() - 1]);
+    // The primes dividing k or modulo which kn is a square: the only odd
+    // primes that can divide some Q_i.
+    let mut fb: Vec<u64> = Vec::with_capacity(fb_size);
+    for p in Sieve::new(2, u64::MAX) {
+        if fb.len() == fb_size {
+            break;
+        }
+        if (n % p).is_zero() {
+            return Some(BigInt::from(p));
+        }
+        if sqrt_mod((&kn % p).to_u64().unwrap(), p).is_some() {
+            fb.push(p);
+        }
+    }
+    let large_bound = fb[fb.len() - 1] * LARGE_PRIME;
+
+    let g = kn.sqrt();
+    let (mut p, mut q, mut a) = (BigInt::zero(), BigInt::one(), g.clone());
+    let (mut a_prev, mut a_cur) = (BigInt::one(), &g % n);
+    let mut relations = Relations::new();
+    let mut target = fb.len() + 1 + EXTRA_RELATIONS;
+    let mut odd = false;
+    loop {
+        p = &a * &q - &p;
+        q = (&kn - &p * &p) / &q;
+        if q.is_one() && odd {
+            // The expansion is periodic from here on.
+            return relations.factor(&fb, n);
+        }
+        if let Some((mut factors, large)) = smooth(&q, &fb, large_bound) {
+            if !odd {
+                factors.push(0);
+            }
+            let y = a_cur.clone();
+            relations.add(Relation { y, factors, large }, n);
+        }
+        if relations.count() >= target {
+            if let Some(d) = relations.factor(&fb, n) {
+                return Some(d);
+            }
+            target += EXTRA_RELATIONS;
+        }
+        a = (&g + &p) / &q;
+        let next = (&a * &a_cur + &a_prev) % n;
+        a_prev = std::mem::replace(&mut a_cur, next);
+        odd = !odd;
+    }
+}
+
+// The columns of the factor base primes dividing q with multiplicity, and
+// the cofactor if it is 1 or a single large prime.
+fn smooth(q: &BigInt, fb: &[u64], large_bound: u64) -> Option<(Vec<usize>, u64)> {
+    let mut q = q.to_u128()?;
+    // Early abort: give up on q if a quarter of the factor base has not
+    // removed some 30% of its bits.
+    let limit = 1u128 << ((128 - q.leading_zeros()) * 7 / 10);
+    let mut columns = Vec::new();
+    for (j, &p) in fb.iter().enumerate() {
+        if j == fb.len() / 4 && q > limit {
+            return None;
+        }
+        let p = p as u128;
+        while q % p == 0 {
+            q /= p;
+            columns.push(j + 1);
+        }
+        if q == 1 {
+            break;
+        }
+    }
+    let large = u64::try_from(q).ok().filter(|&l| l < large_bound)?;
+    Some((columns, large))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_cfrac() {
+        let n = bi!("461320114029656922443744803307", 10);
+        let f = Cfrac::new(n).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "545288950911421 * 846010382676167");
+    }
+}
@@ -1,9 +1,20 @@
-use clap::{ValueEnum, Parser};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, ValueEnum, Parser, Subcommand};
+use crate::expr;
+use num::BigInt;
+
+// Largest stage 1 bound. Stage 1 walks a table of every prime up to B1, which
+// is some 400 MB at this bound.
+const MAX_B1: u64 = 1_000_000_000;
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cli {
-    #[clap(short, long, value_enum, default_value_t = Algorithm::Trial)]
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
+    #[clap(short, long, value_enum, default_value_t = Algorithm::Auto)]
     pub algorithm: Algorithm,
 
     #[clap(short, long, value_enum, default_value_t = OutputFormat::Expr)]
@@ -12,15 +23,104 @@ pub struct Cli {
     #[clap(short, long, default_value_t = -1)]
     pub jobs: i8,
 
-    pub n: String,
+    /// Stage 1 bound of p-1, p+1 and ECM [default: 10000, or 2000 for ECM]
+    #[clap(long, value_parser = clap::value_parser!(u64).range(..=MAX_B1))]
+    pub b1: Option<u64>,
+
+    /// Stage 2 bound of p-1, p+1 and ECM [default: 100 * B1]
+    #[clap(long)]
+    pub b2: Option<u64>,
+
+    /// Family of the ECM curves
+    #[clap(long, value_enum, default_value_t = Curve::Montgomery)]
+    pub curve: Curve,
+
+    /// Rational torsion of the Edwards curves
+    #[clap(long, value_enum, default_value_t = Torsion::Z12)]
+    pub torsion: Torsion,
+
+    /// Run the single ECM curve with this parameter
+    #[clap(long)]
+    pub sigma: Option<u64>,
+
+    /// Number of ECM curves to try before giving up
+    #[clap(long)]
+    pub curves: Option<usize>,
+
+    /// Number of random starting values P0 for p+1
+    #[clap(long)]
+    pub seeds: Option<usize>,
+
+    /// Prove the prime factors and print a certificate for them
+    #[clap(long)]
+    pub certify: bool,
+
+    /// Factor one integer per line of FILE, or of stdin for "-"
+    #[clap(long, value_name = "FILE", conflicts_with = "n")]
+    pub input: Option<String>,
+
+    /// The integer to factor, or "-" to read one per line from stdin
+    #[clap(required_unless_present = "input")]
+    pub n: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Check a primality certificate read from FILE, or stdin for "-"
+    VerifyCert { file: String },
+    /// List the primes from A to B
+    Primes { a: u64, b: u64 },
+    /// Count the primes up to X
+    Pi { x: u64 },
+    /// Print the smallest prime above N
+    #[clap(name = "nextprime")]
+    NextPrime {
+        #[clap(value_parser = expr::parse)]
+        n: BigInt,
+    },
+    /// Print the largest prime below N
+    #[clap(name = "prevprime")]
+    PrevPrime {
+        #[clap(value_parser = expr::parse)]
+        n: BigInt,
+    },
+    /// Factor every integer from A to B, one line each
+    FactorRange {
+        #[clap(value_parser = clap::value_parser!(u64).range(1..))]
+        a: u64,
+        b: u64,
+        #[clap(short, long, value_enum, default_value_t = OutputFormat::Expr)]
+        output_format: OutputFormat,
+    },
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Algorithm {
     Auto,
     Trial,
-    ECM,
+    Ecm,
     Fermat,
+    Rho,
+    Pm1,
+    Pp1,
+    QS,
+    Cfrac,
+    Squfof,
+    Lehman,
+    Hart,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Curve {
+    Weierstrass,
+    Montgomery,
+    Edwards,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Torsion {
+    Z12,
+    Z2z8,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -33,5 +133,14 @@ pub enum OutputFormat {
 
 pub fn parse() -> Cli {
     let args = Cli::parse();
+    // Weierstrass curves are drawn at random and have no sigma to select.
+    if args.sigma.is_some() && args.curve == Curve::Weierstrass {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--sigma selects Montgomery and Edwards curves, not --curve weierstrass",
+            )
+            .exit();
+    }
     args
 }
@@ -0,0 +1,147 @@
+use crate::algs::{perfect_power, primality, Primality};
+use crate::native;
+use crate::traits::{Factors, Splitter, Status};
+use num::bigint::BigInt;
+use num::{Integer, One, Zero};
+
+// The factorization of n > 0 by repeated splitting. Cofactors are kept
+// pairwise coprime with their multiplicities, so that a divisor sharing primes
+// with its cofactor, as d = p splitting p^2 q does, merges exponents instead of
+// losing them. Perfect powers are reduced to their roots first. A composite
+// the splitter gives up on stays in the result as it is, unless the splitter
+// opts into the native engine for those that fit in a machine word.
+pub fn factorize<S: Splitter + ?Sized>(n: &BigInt, splitter: &S) -> Option<Factors> {
+    if *n < BigInt::one() {
+        return None;
+    }
+    let mut factors = Factors::new(None);
+    let mut composites: Vec<(BigInt, u32)> = vec![(n.clone(), 1)];
+    while let Some((m, e)) = composites.pop() {
+        if m.is_one() {
+            continue;
+        }
+        let status = primality(&m);
+        if status != Primality::Composite {
+            factors.insert(m, e, status.into());
+            continue;
+        }
+        if let Some((r, k)) = perfect_power(&m) {
+            composites.push((r, e * k));
+            continue;
+        }
+        match splitter.split(&m) {
+            Some(d) if d > BigInt::one() && d < m && (&m % &d).is_zero() => {
+                let mut parts = Vec::new();
+                refine(&mut parts, &m / &d, e);
+                refine(&mut parts, d, e);
+                composites.extend(parts);
+            }
+            _ => match (splitter.fallback() && m.bits() <= 64)
+                .then(|| native::factors(&m))
+                .flatten()
+            {
+                Some(f) => {
+                    for f in f.get_factors_vector() {
+                        let (p, k) = f.pe();
+                        factors.insert(p, e * k, f.status());
+                    }
+                }
+                None => factors.insert(m, e, Status::Composite),
+            },
+        }
+    }
+    Some(factors)
+}
+
+// Adds m^e to the pairwise coprime `base`, splitting m and any entry it shares
+// a factor with into their gcd and the two quotients.
+fn refine(base: &mut Vec<(BigInt, u32)>, m: BigInt, e: u32) {
+    if m.is_one() {
+        return;
+    }
+    for i in 0..base.len() {
+        let g = base[i].0.gcd(&m);
+        if g.is_one() {
+            continue;
+        }
+        let (b, f) = base.swap_remove(i);
+        refine(base, &b / &g, f);
+        refine(base, &m / &g, e);
+        refine(base, g, e + f);
+        return;
+    }
+    base.push((m, e));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::ToPrimitive;
+
+    // Splits off the smallest prime factor, except that it gives up on 1001
+    // and 2^64 + 1, and returns a bad divisor of 35. The flag opts into the
+    // native fallback.
+    struct Smallest(bool);
+
+    impl Splitter for Smallest {
+        fn fallback(&self) -> bool {
+            self.0
+        }
+        fn split(&self, n: &BigInt) -> Option<BigInt> {
+            if *n == BigInt::from(u64::MAX) + 2u32 {
+                return None;
+            }
+            match n.to_u32() {
+                Some(1001) => None,
+                Some(35) => Some(n.clone()),
+                _ => (2u32..).map(BigInt::from).find(|d| (n % d).is_zero()),
+            }
+        }
+    }
+
+    // Splits p^2 q as p * pq.
+    struct Overlapping;
+
+    impl Splitter for Overlapping {
+        fn split(&self, n: &BigInt) -> Option<BigInt> {
+            let p = (2u32..).map(BigInt::from).find(|d| (n % d).is_zero())?;
+            Some(if (n % (&p * &p)).is_zero() { &p * &p } else { p })
+        }
+    }
+
+    #[test]
+    fn test_driver() {
+        let f = factorize(&BigInt::from(2u32 * 2 * 3 * 5 * 5 * 5 * 13), &Smallest(true)).unwrap();
+        assert_eq!(f.get_factors_expr(), "2^2 * 3 * 5^3 * 13");
+        let f = factorize(&BigInt::from(3u32 * 7 * 11 * 13), &Smallest(true)).unwrap();
+        assert_eq!(f.get_factors_expr(), "3 * 7 * 11 * 13");
+        let f = factorize(&BigInt::from(3u32 * 5 * 7), &Smallest(true)).unwrap();
+        assert_eq!(f.get_factors_expr(), "3 * 5 * 7");
+
+        // Without the fallback, a word-sized composite given up on stays too.
+        let f = factorize(&BigInt::from(3u32 * 7 * 11 * 13), &Smallest(false)).unwrap();
+        assert_eq!(f.get_factors_expr(), "3 * C1001");
+        assert_eq!(f.unfactored(), BigInt::from(1001u32));
+        let f = factorize(&BigInt::from(3u32 * 5 * 7), &Smallest(false)).unwrap();
+        assert_eq!(f.get_factors_expr(), "3 * C35");
+
+        // Beyond a machine word, what the splitter gives up on stays.
+        let n = BigInt::from(1001u32) * (BigInt::from(u64::MAX) + 2u32);
+        let f = factorize(&n, &Smallest(true)).unwrap();
+        assert_eq!(f.get_factors_expr(), "7 * 11 * 13 * C18446744073709551617");
+        assert_eq!(f.unfactored(), BigInt::from(u64::MAX) + 2u32);
+        assert!(!f.is_complete());
+        let f = factorize(&BigInt::from(2u32.pow(10) * 3 * 3 * 17), &Overlapping).unwrap();
+        assert_eq!(f.get_factors_expr(), "2^10 * 3^2 * 17");
+        assert_eq!(factorize(&BigInt::from(7u32), &Smallest(true)).unwrap().get_factors_expr(), "7");
+        assert_eq!(factorize(&BigInt::one(), &Smallest(true)).unwrap().get_factors_expr(), "");
+        assert!(factorize(&BigInt::zero(), &Smallest(true)).is_none());
+
+        let mut base = Vec::new();
+        refine(&mut base, BigInt::from(12u32), 1);
+        refine(&mut base, BigInt::from(18u32), 2);
+        base.sort();
+        let expected: Vec<(BigInt, u32)> = vec![(BigInt::from(2u32), 4), (BigInt::from(3u32), 5)];
+        assert_eq!(base, expected);
+    }
+}
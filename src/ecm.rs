@@ -1,70 +1,514 @@
-use crate::consts::PRIMES as primes;
-use crate::elliptic_curve::{scalar_mul_for_factorization, EllipticCurve};
+use crate::algs::prime_power;
+use crate::cli::{Curve, Torsion};
+use crate::driver;
+use crate::elliptic_curve::{
+    add_for_factorization, scalar_mul_for_factorization, EdwardsCurve, EdwardsPoint,
+    EllipticCurve, MontgomeryCurve, MontgomeryPoint, Point,
+};
 use crate::galois_field::GaloisField;
-use crate::traits::{Factorizer, Factors};
+use crate::native::{Montgomery, Word};
+use crate::parallel::{race, stopped};
+use crate::sieve;
+use crate::traits::{Factorizer, Factors, Splitter};
 use num::bigint::BigInt;
+use num::{Integer, One, ToPrimitive};
 use num_bigint::RandBigInt;
+use rand::Rng;
+use std::sync::atomic::AtomicBool;
+use std::sync::Mutex;
 
-pub struct ECM {
+pub const DEFAULT_B1: u64 = 2_000;
+pub const DEFAULT_B2: u64 = 200_000;
+
+// Curves tried before giving up, as many as GMP-ECM suggests for factors of
+// 15 digits at the default B1.
+pub const DEFAULT_CURVES: usize = 25;
+
+// Giant step of stage 2; every prime above B1 is m * D +/- j with j < D / 2
+// and m >= 1, as stage 1 always runs to at least D / 2.
+const D: u64 = 2 * 3 * 5 * 7;
+
+// Suyama parameters are drawn from the same range as GMP-ECM's.
+const SIGMA_RANGE: std::ops::Range<u64> = 6..1 << 32;
+
+pub struct Ecm {
     n: BigInt,
+    b1: u64,
+    b2: u64,
+    curve: Curve,
+    torsion: Torsion,
+    sigma: Option<u64>,
+    curves: usize,
+    jobs: usize,
+    found: Mutex<Vec<u64>>,
 }
-impl ECM {
+impl Ecm {
     pub fn new(n: BigInt) -> Self {
-        Self { n }
+        Self {
+            n,
+            b1: DEFAULT_B1,
+            b2: DEFAULT_B2,
+            curve: Curve::Montgomery,
+            torsion: Torsion::Z12,
+            sigma: None,
+            curves: DEFAULT_CURVES,
+            jobs: 1,
+            found: Mutex::new(Vec::new()),
+        }
+    }
+    pub fn bounds(mut self, b1: u64, b2: u64) -> Self {
+        self.b1 = b1;
+        self.b2 = b2.max(b1);
+        self
+    }
+    pub fn curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+    // The family Edwards curves are drawn from.
+    pub fn torsion(mut self, torsion: Torsion) -> Self {
+        self.torsion = torsion;
+        self
+    }
+    // Runs only the curve with this parameter: Suyama's sigma for Montgomery
+    // curves, the multiple of the generating point for Edwards curves. The
+    // random Weierstrass curves have no such parameter.
+    pub fn sigma(mut self, sigma: Option<u64>) -> Self {
+        self.sigma = sigma;
+        self
+    }
+    // Number of curves to try on each composite before giving up on it.
+    pub fn curves(mut self, curves: usize) -> Self {
+        self.curves = curves;
+        self
+    }
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+    // The sigma of each curve that found a factor so far, in order, so that
+    // the curve can be run again.
+    pub fn sigmas(&self) -> Vec<u64> {
+        self.found.lock().unwrap().clone()
     }
 }
 
-impl Factorizer for ECM {
+impl Factorizer for Ecm {
     fn factorize(&self) -> Option<Factors> {
-        factorize(&self.n)
+        driver::factorize(&self.n, self)
     }
 }
 
-fn factorize(n: &BigInt) -> Option<Factors> {
-    let n = n.clone();
+// Up to `curves` curves of the chosen kind until one finds a factor, or only
+// the curve of the given sigma. The sigma of the successful curve is kept for `sigmas`;
+// Weierstrass curves are random and have none.
+impl Splitter for Ecm {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        if n.is_even() {
+            return Some(BigInt::from(2u32));
+        }
+        let (b1, b2) = (self.b1.max(D / 2), self.b2);
+        let curves = if self.sigma.is_some() { 1 } else { self.curves };
+        let (d, sigma) = race(self.jobs, curves, |_, stop| {
+            if self.curve == Curve::Weierstrass {
+                return weierstrass_curve(n, b1, b2, stop).map(|d| (d, None));
+            }
+            let sigma = self
+                .sigma
+                .unwrap_or_else(|| rand::thread_rng().gen_range(SIGMA_RANGE));
+            let d = match self.curve {
+                Curve::Edwards => edwards_curve(n, b1, b2, self.torsion, sigma, stop),
+                _ => montgomery_curve(n, b1, b2, sigma, stop),
+            };
+            d.map(|d| (d, Some(sigma)))
+        })?;
+        self.found.lock().unwrap().extend(sigma);
+        Some(d)
+    }
+}
+
+// Lenstra's ECM on up to `curves` random Montgomery curves, `jobs` at a time.
+pub fn split(n: &BigInt, b1: u64, b2: u64, curves: usize, jobs: usize) -> Option<BigInt> {
+    if n.is_even() {
+        return Some(BigInt::from(2));
+    }
+    let b1 = b1.max(D / 2);
+    race(jobs, curves, |_, stop| {
+        let sigma = rand::thread_rng().gen_range(SIGMA_RANGE);
+        montgomery_curve(n, b1, b2, sigma, stop)
+    })
+}
+
+fn nontrivial(d: BigInt, n: &BigInt) -> Option<BigInt> {
+    if d.is_one() || d == *n {
+        None
+    } else {
+        Some(d)
+    }
+}
+
+fn weierstrass_curve(n: &BigInt, b1: u64, b2: u64, stop: &AtomicBool) -> Option<BigInt> {
     let mut rng = rand::thread_rng();
     let low = BigInt::from(2);
     let high = n.clone();
-    let l = 242u64;
-
-    loop {
-        let f = GaloisField::GaloisField(&n);
-        let x0 = f.new(&rng.gen_bigint_range(&low, &high));
-        let y0 = f.new(&rng.gen_bigint_range(&low, &high));
-        let a = f.new(&rng.gen_bigint_range(&low, &high));
-        let b = y0.clone().pow(&BigInt::from(2))?
-            - x0.clone().pow(&BigInt::from(3))?
-            - a.clone() * x0.clone();
-        let e = EllipticCurve::new(&f, &a.value, &b.value);
-        let g = e.new_point(&x0.value, &y0.value);
-
-        for p in primes {
-            let mut m: u64 = p.clone();
-            //let mut pg; // Previous g
-            let cg = (BigInt::from(2) * g.clone())?; // Current g
-
-            while m.clone() * p.clone() <= l {
-                m *= p.clone();
+
+    let f = GaloisField::GaloisField(n);
+    let x0 = f.new(&rng.gen_bigint_range(&low, &high));
+    let y0 = f.new(&rng.gen_bigint_range(&low, &high));
+    let a = f.new(&rng.gen_bigint_range(&low, &high));
+    let b = y0.clone().pow(&BigInt::from(2))?
+        - x0.clone().pow(&BigInt::from(3))?
+        - a.clone() * x0.clone();
+    let e = EllipticCurve::new(&f, &a.value, &b.value);
+    let g = e.new_point(&x0.value, &y0.value);
+
+    let g = match stage1(g, b1, stop) {
+        Ok(g) => g,
+        Err(d) => return nontrivial(d, n),
+    };
+    if g == e.o() {
+        return None;
+    }
+    match stage2(g, n, b1, b2, stop) {
+        Ok(d) | Err(d) => nontrivial(d, n),
+    }
+}
+
+// Multiplies g by every prime power up to b1. A failed inversion on the way
+// is returned as Err(gcd); being stopped as Err(1).
+fn stage1(mut g: Point, b1: u64, stop: &AtomicBool) -> Result<Point, BigInt> {
+    for p in sieve::primes(b1).iter().copied() {
+        if stopped(stop) {
+            return Err(BigInt::one());
+        }
+        g = scalar_mul_for_factorization(BigInt::from(prime_power(p, b1)), g)?;
+    }
+    Ok(g)
+}
+
+// Baby-step giant-step continuation over the primes q = m * D +/- j in
+// (b1, b2]. If q * g vanishes mod p then m * D * g = +/- j * g mod p, so the
+// x-coordinates agree and their difference is collected into one product
+// whose gcd with n is returned.
+fn stage2(
+    g: Point,
+    n: &BigInt,
+    b1: u64,
+    b2: u64,
+    stop: &AtomicBool,
+) -> Result<BigInt, BigInt> {
+    let f = GaloisField::GaloisField(n);
+    let g2 = add_for_factorization(g.clone(), g.clone())?;
+    let mut babies: Vec<Option<GaloisField>> = vec![None; (D / 2) as usize];
+    let mut jg = g.clone();
+    for j in (1..D / 2).step_by(2) {
+        if j.gcd(&D) == 1 {
+            babies[j as usize] = Some(f.new(&jg.x()));
+        }
+        jg = add_for_factorization(jg, g2.clone())?;
+    }
+
+    let dg = scalar_mul_for_factorization(BigInt::from(D), g.clone())?;
+    let mut m = (b1 / D).max(1);
+    let mut mg = scalar_mul_for_factorization(BigInt::from(m * D), g)?;
+    let mut x = f.new(&mg.x());
+    let mut acc = f.one();
+    for (k, j) in sieve::giant_steps(b1 + 1, b2, D) {
+        while m < k {
+            if stopped(stop) {
+                return Ok(BigInt::one());
+            }
+            mg = add_for_factorization(mg, dg.clone())?;
+            x = f.new(&mg.x());
+            m += 1;
+        }
+        if let Some(xj) = &babies[j as usize] {
+            acc = acc * (x.clone() - xj.clone());
+        }
+    }
+    Ok(acc.value.gcd(n))
+}
+
+fn montgomery_curve(
+    n: &BigInt,
+    b1: u64,
+    b2: u64,
+    sigma: u64,
+    stop: &AtomicBool,
+) -> Option<BigInt> {
+    if let Some(m) = n.to_u64() {
+        return montgomery_curve_native(m, b1, b2, sigma, stop).map(Into::into);
+    }
+    if let Some(m) = n.to_u128() {
+        return montgomery_curve_native(m, b1, b2, sigma, stop).map(Into::into);
+    }
+    let f = GaloisField::GaloisField(n);
+    let (e, mut g) = match MontgomeryCurve::suyama(&f, &BigInt::from(sigma)) {
+        Ok(c) => c,
+        Err(d) => return nontrivial(d, n),
+    };
+    for p in sieve::primes(b1).iter().copied() {
+        if stopped(stop) {
+            return None;
+        }
+        g = e.ladder(&BigInt::from(prime_power(p, b1)), &g);
+    }
+    let d = g.z.value.gcd(n);
+    if !d.is_one() {
+        return nontrivial(d, n);
+    }
+    nontrivial(montgomery_stage2(&e, &g, n, b1, b2, stop), n)
+}
+
+// Same baby-step giant-step layout as `stage2`, with the x-coordinate
+// differences taken projectively as X_m Z_j - X_j Z_m.
+fn montgomery_stage2(
+    e: &MontgomeryCurve,
+    g: &MontgomeryPoint,
+    n: &BigInt,
+    b1: u64,
+    b2: u64,
+    stop: &AtomicBool,
+) -> BigInt {
+    let f = GaloisField::GaloisField(n);
+    let g2 = e.double(g);
+    let mut babies: Vec<Option<MontgomeryPoint>> = vec![None; (D / 2) as usize];
+    let (mut prev, mut jg) = (g.clone(), g.clone());
+    for j in (1..D / 2).step_by(2) {
+        if j.gcd(&D) == 1 {
+            babies[j as usize] = Some(jg.clone());
+        }
+        let next = if j == 1 {
+            e.add(&g2, g, g)
+        } else {
+            e.add(&jg, &g2, &prev)
+        };
+        prev = jg;
+        jg = next;
+    }
+
+    let dg = e.ladder(&BigInt::from(D), g);
+    let mut m = (b1 / D).max(1);
+    let mut mg = e.ladder(&BigInt::from(m * D), g);
+    let mut next = e.ladder(&BigInt::from((m + 1) * D), g);
+    let mut acc = f.one();
+    for (k, j) in sieve::giant_steps(b1 + 1, b2, D) {
+        while m < k {
+            if stopped(stop) {
+                return BigInt::one();
+            }
+            let after = e.add(&next, &dg, &mg);
+            mg = next;
+            next = after;
+            m += 1;
+        }
+        if let Some(jg) = &babies[j as usize] {
+            acc = acc * (mg.x.clone() * jg.z.clone() - jg.x.clone() * mg.z.clone());
+        }
+    }
+    acc.value.gcd(n)
+}
+
+// Montgomery curves on machine words, for n below 2^128; Edwards and
+// Weierstrass curves always run on BigInt. The constant a24 = a / c is kept
+// as a fraction so that no inverse is needed: doubling then scales both
+// coordinates by c, which leaves the projective point as it is.
+struct NativeCurve<W: Word> {
+    m: Montgomery<W>,
+    a: W,
+    c: W,
+}
+
+impl<W: Word> NativeCurve<W> {
+    fn double(&self, (x, z): (W, W)) -> (W, W) {
+        let m = &self.m;
+        let (s, d) = (m.add(x, z), m.sub(x, z));
+        let (ss, dd) = (m.mul(s, s), m.mul(d, d));
+        let t = m.sub(ss, dd);
+        let ddc = m.mul(dd, self.c);
+        (m.mul(ss, ddc), m.mul(t, m.add(ddc, m.mul(self.a, t))))
+    }
+
+    // P + Q given their difference P - Q.
+    fn add(&self, p: (W, W), q: (W, W), diff: (W, W)) -> (W, W) {
+        let m = &self.m;
+        let u = m.mul(m.sub(p.0, p.1), m.add(q.0, q.1));
+        let v = m.mul(m.add(p.0, p.1), m.sub(q.0, q.1));
+        let (s, d) = (m.add(u, v), m.sub(u, v));
+        (m.mul(diff.1, m.mul(s, s)), m.mul(diff.0, m.mul(d, d)))
+    }
+
+    fn ladder(&self, k: u64, p: (W, W)) -> (W, W) {
+        if k == 0 {
+            return (self.m.one, W::zero());
+        }
+        let (mut r0, mut r1) = (p, self.double(p));
+        for i in (0..63 - k.leading_zeros()).rev() {
+            if k >> i & 1 == 1 {
+                r0 = self.add(r1, r0, p);
+                r1 = self.double(r1);
+            } else {
+                r1 = self.add(r0, r1, p);
+                r0 = self.double(r0);
             }
+        }
+        r0
+    }
+}
+
+fn montgomery_curve_native<W: Word>(
+    n: W,
+    b1: u64,
+    b2: u64,
+    sigma: u64,
+    stop: &AtomicBool,
+) -> Option<W> {
+    let m = Montgomery::new(n);
+    let w = |k: u64| m.to(W::from_u64(k));
+    let s = w(sigma);
+    let u = m.sub(m.mul(s, s), w(5));
+    let v = m.mul(s, w(4));
+    let u3 = m.mul(m.mul(u, u), u);
+    let vu = m.sub(v, u);
+    let a = m.mul(m.mul(m.mul(vu, vu), vu), m.add(m.mul(u, w(3)), v));
+    let den = m.mul(m.mul(u3, v), w(16));
+    let d = den.gcd(&n);
+    if !d.is_one() {
+        return (d != n).then_some(d);
+    }
+    let mut g = (u3, m.mul(m.mul(v, v), v));
+    let e = NativeCurve { m, a, c: den };
+
+    for p in sieve::primes(b1).iter().copied() {
+        if stopped(stop) {
+            return None;
+        }
+        g = e.ladder(prime_power(p, b1), g);
+    }
+    let d = g.1.gcd(&n);
+    if d.is_one() {
+        let d = montgomery_stage2_native(&e, g, b1, b2, stop).gcd(&n);
+        return (!d.is_one() && d != n).then_some(d);
+    }
+    (d != n).then_some(d)
+}
+
+fn montgomery_stage2_native<W: Word>(
+    e: &NativeCurve<W>,
+    g: (W, W),
+    b1: u64,
+    b2: u64,
+    stop: &AtomicBool,
+) -> W {
+    let m = &e.m;
+    let g2 = e.double(g);
+    let mut babies: Vec<Option<(W, W)>> = vec![None; (D / 2) as usize];
+    let (mut prev, mut jg) = (g, g);
+    for j in (1..D / 2).step_by(2) {
+        if j.gcd(&D) == 1 {
+            babies[j as usize] = Some(jg);
+        }
+        let next = if j == 1 {
+            e.add(g2, g, g)
+        } else {
+            e.add(jg, g2, prev)
+        };
+        prev = jg;
+        jg = next;
+    }
+
+    let dg = e.ladder(D, g);
+    let mut i = (b1 / D).max(1);
+    let mut mg = e.ladder(i * D, g);
+    let mut next = e.ladder((i + 1) * D, g);
+    let mut acc = m.one;
+    for (k, j) in sieve::giant_steps(b1 + 1, b2, D) {
+        while i < k {
+            if stopped(stop) {
+                return W::one();
+            }
+            let after = e.add(next, dg, mg);
+            mg = next;
+            next = after;
+            i += 1;
+        }
+        if let Some(jg) = babies[j as usize] {
+            acc = m.mul(acc, m.sub(m.mul(mg.0, jg.1), m.mul(jg.0, mg.1)));
+        }
+    }
+    acc
+}
+
+fn edwards_curve(
+    n: &BigInt,
+    b1: u64,
+    b2: u64,
+    torsion: Torsion,
+    k: u64,
+    stop: &AtomicBool,
+) -> Option<BigInt> {
+    let f = GaloisField::GaloisField(n);
+    let curve = match torsion {
+        Torsion::Z12 => EdwardsCurve::z12(&f, &BigInt::from(k)),
+        Torsion::Z2z8 => EdwardsCurve::z2z8(&f, &BigInt::from(k)),
+    };
+    let (e, mut g) = match curve {
+        Ok(c) => c,
+        Err(d) => return nontrivial(d, n),
+    };
+    for p in sieve::primes(b1).iter().copied() {
+        if stopped(stop) {
+            return None;
+        }
+        g = e.mul(&BigInt::from(prime_power(p, b1)), &g);
+    }
+    // The identity is (0 : 1 : 1 : 0), so X vanishes mod p once the order of
+    // g modulo p divides the stage 1 multiplier.
+    let d = g.x.value.gcd(n);
+    if !d.is_one() {
+        return nontrivial(d, n);
+    }
+    nontrivial(edwards_stage2(&e, &g, n, b1, b2, stop), n)
+}
 
-            let xg = scalar_mul_for_factorization(BigInt::from(m), g.clone());
-            println!("{p} | {xg:?}");
-            //for k in 1..=m {
-            //    pg = cg.clone();
-            //    cg = (cg.clone() + g.clone())?;
-            //    println!("{k}/{m}");
-            //    if cg == e.o() {
-            //        let m = pg.x() - g.x();
-            //        let q = n.gcd(&m);
-            //        println!("{k} | pg: {pg}  cg: {cg}  m: {m}  found: {q}    n: {n}");
-            //        if q != BigInt::one() && q != n {
-            //            println!("FOUND!!!!: q: {q}");
-            //            break;
-            //        }
-            //    }
-            //}
+// Same baby-step giant-step layout as `stage2`. On Edwards curves -(x, y) is
+// (-x, y), so the y-coordinates are compared as Y_m Z_j - Y_j Z_m.
+fn edwards_stage2(
+    e: &EdwardsCurve,
+    g: &EdwardsPoint,
+    n: &BigInt,
+    b1: u64,
+    b2: u64,
+    stop: &AtomicBool,
+) -> BigInt {
+    let f = GaloisField::GaloisField(n);
+    let g2 = e.double(g);
+    let mut babies: Vec<Option<EdwardsPoint>> = vec![None; (D / 2) as usize];
+    let mut jg = g.clone();
+    for j in (1..D / 2).step_by(2) {
+        if j.gcd(&D) == 1 {
+            babies[j as usize] = Some(jg.clone());
         }
+        jg = e.add(&jg, &g2);
     }
+
+    let dg = e.mul(&BigInt::from(D), g);
+    let mut m = (b1 / D).max(1);
+    let mut mg = e.mul(&BigInt::from(m * D), g);
+    let mut acc = f.one();
+    for (k, j) in sieve::giant_steps(b1 + 1, b2, D) {
+        while m < k {
+            if stopped(stop) {
+                return BigInt::one();
+            }
+            mg = e.add(&mg, &dg);
+            m += 1;
+        }
+        if let Some(jg) = &babies[j as usize] {
+            acc = acc * (mg.y.clone() * jg.z.clone() - jg.y.clone() * mg.z.clone());
+        }
+    }
+    acc.value.gcd(n)
 }
 
 #[cfg(test)]
@@ -72,14 +516,72 @@ mod tests {
     use super::*;
     use crate::bi;
     use num::bigint::BigInt;
+    use num::One;
 
     #[test]
     fn test_ecm() {
-        //let n = bi!("121439531096594251777", 10);
-        //let n = bi!("455839", 10);
         let n = bi!("835791", 10);
-        let ff = ECM::new(n);
-        let f = ff.factorize();
-        println!("f: {f:?}");
+        for curve in [Curve::Weierstrass, Curve::Montgomery, Curve::Edwards] {
+            let ff = Ecm::new(n.clone()).curve(curve);
+            let f = ff.factorize().unwrap();
+            assert_eq!(f.n(), n);
+            let ps = f.get_factors();
+            assert!(ps.keys().all(|p| *p > BigInt::one() && *p < n));
+        }
+
+        // Modulo primes as small as those of 835791 a group order divisible
+        // by 16 is always smooth, so every curve would find all of them at once.
+        let n = BigInt::from(1000003u64 * 1000033u64);
+        let f = Ecm::new(n)
+            .curve(Curve::Edwards)
+            .torsion(Torsion::Z2z8)
+            .factorize()
+            .unwrap();
+        assert_eq!(f.get_factors_expr(), "1000003 * 1000033");
+    }
+
+    #[test]
+    fn test_ecm_stage2() {
+        // With B2 = 5000 about every other curve succeeds, against one in a
+        // few hundred for stage 1 alone.
+        let n = BigInt::from(1000003u64 * 1000033u64);
+        let d = split(&n, 20, 5000, 200, 2).unwrap();
+        assert!(d == BigInt::from(1000003u64) || d == BigInt::from(1000033u64));
+    }
+
+    #[test]
+    fn test_ecm_sigma() {
+        // Modulo 123457 the curve with sigma = 27 has order 2^5 * 3 * 1283,
+        // so with B1 = 50 it needs stage 2 to reach 1283.
+        let n = bi!("123457000067530979", 10);
+        let ecm = Ecm::new(n.clone()).bounds(50, 2000).sigma(Some(27));
+        assert_eq!(ecm.factorize().unwrap().get_factors_expr(), "123457 * 1000000000547");
+        assert_eq!(ecm.sigmas(), [27]);
+        assert!(Ecm::new(n.clone()).bounds(50, 1000).sigma(Some(27)).split(&n).is_none());
+
+        // The same curve on 128-bit words, and above 2^128 on BigInt.
+        for n in [
+            bi!("123457000000000000000000000007037049", 10),
+            bi!("123457000000000000000000000000000001530496429", 10),
+        ] {
+            let ecm = Ecm::new(n.clone()).bounds(50, 2000).sigma(Some(27));
+            assert_eq!(ecm.split(&n), Some(BigInt::from(123457)));
+            assert!(Ecm::new(n.clone()).bounds(50, 1000).sigma(Some(27)).split(&n).is_none());
+        }
+
+        // Stage 1 to B1 = 10 alone misses the factor with sigma = 8, but the
+        // primes up to D / 2 are never left out.
+        assert!(montgomery_curve(&n, 10, 10, 8, &AtomicBool::new(false)).is_none());
+        assert!(Ecm::new(n.clone()).bounds(10, 100).sigma(Some(8)).split(&n).is_some());
+    }
+
+    #[test]
+    fn test_ecm_curves() {
+        // No curve gets near factors of 22 digits with B1 = 10, so the
+        // composite is left as it is after the given number of curves.
+        let n = bi!("1000000000000000000310000000000000000022581", 10);
+        let f = Ecm::new(n.clone()).bounds(10, 100).curves(4).factorize().unwrap();
+        assert_eq!(f.unfactored(), n);
+        assert!(!f.is_complete());
     }
 }
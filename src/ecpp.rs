@@ -0,0 +1,297 @@
+use crate::algs::{is_prime, jacobi};
+use crate::elliptic_curve::{scalar_mul_for_factorization, EllipticCurve};
+use crate::galois_field::GaloisField;
+use crate::hilbert::HILBERT;
+use crate::sieve;
+use num::bigint::BigInt;
+use num::{Integer, One, Zero};
+
+// Elliptic curve primality proving after Atkin and Morain. When 4n = u^2 +
+// |D| v^2 for a discriminant D of the table, the curves over F_n with complex
+// multiplication by D have n + 1 - t points for a handful of traces t. Once
+// one of those orders is a small cofactor times a probable prime q > (n^(1/4)
+// + 1)^2, a point of order q on the right curve proves n prime, provided q is.
+
+// Primes divided out of the candidate orders.
+const TRIAL_BOUND: u64 = 1 << 16;
+
+// Points tried on a twist before deciding it has the wrong order.
+const POINTS: u32 = 16;
+
+// Attempts at splitting a class polynomial before giving up on its roots.
+const ATTEMPTS: u32 = 64;
+
+pub struct Order {
+    pub d: i64,
+    pub m: BigInt,
+    pub q: BigInt,
+}
+
+// q > (n^(1/4) + 1)^2, checked as (floor(sqrt(q)) - 1)^4 > n.
+pub fn large_enough(q: &BigInt, n: &BigInt) -> bool {
+    *q > BigInt::one() && (q.sqrt() - 1u32).pow(4) > *n
+}
+
+// Orders m = n + 1 - t that factor as a cofactor below TRIAL_BOUND smooth
+// times a large enough probable prime q, the smallest q first.
+pub fn orders(n: &BigInt) -> Vec<Order> {
+    let mut orders: Vec<Order> = Vec::new();
+    for &(d, _) in HILBERT.iter() {
+        if jacobi(&BigInt::from(d), n) != 1 {
+            continue;
+        }
+        let Some((u, v)) = cornacchia(n, d) else {
+            continue;
+        };
+        for t in traces(d, u, v) {
+            for t in [t.clone(), -t] {
+                let m: BigInt = n + 1u32 - t;
+                let mut q = m.clone();
+                for &p in sieve::primes(TRIAL_BOUND).iter() {
+                    while (&q % p).is_zero() {
+                        q /= p;
+                    }
+                }
+                if q < m && large_enough(&q, n) && is_prime(&q) {
+                    orders.push(Order { d, m, q });
+                }
+            }
+        }
+    }
+    orders.sort_by(|a, b| a.q.cmp(&b.q).then(a.m.cmp(&b.m)));
+    orders.dedup_by(|a, b| a.m == b.m);
+    orders
+}
+
+// Cornacchia's algorithm for 4n = u^2 + |d| v^2, n an odd prime.
+fn cornacchia(n: &BigInt, d: i64) -> Option<(BigInt, BigInt)> {
+    let f = GaloisField::GaloisField(n);
+    let mut x0 = f.new(&BigInt::from(d)).sqrt()?.value;
+    if x0.is_odd() != (d % 2 != 0) {
+        x0 = n - x0;
+    }
+    let (mut a, mut b): (BigInt, BigInt) = (n * 2u32, x0);
+    let l = (n * 4u32).sqrt();
+    while b > l {
+        let r = &a % &b;
+        a = b;
+        b = r;
+    }
+    let rest: BigInt = n * 4u32 - &b * &b;
+    let (c, r) = rest.div_rem(&BigInt::from(-d));
+    let v = c.sqrt();
+    (r.is_zero() && &v * &v == c).then_some((b, v))
+}
+
+// Traces up to sign: D = -3 and D = -4 have six and four twists.
+fn traces(d: i64, u: BigInt, v: BigInt) -> Vec<BigInt> {
+    match d {
+        -3 => vec![(&u + &v * 3u32) / 2u32, (&u - &v * 3u32) / 2u32, u],
+        -4 => vec![v * 2u32, u],
+        _ => vec![u],
+    }
+}
+
+// A curve y^2 = x^3 + ax + b with CM by d and m points, and a point P on it
+// with (m / q) P != O and m P = O, as (a, b, x, y).
+pub fn curve(
+    n: &BigInt,
+    d: i64,
+    m: &BigInt,
+    q: &BigInt,
+) -> Option<(BigInt, BigInt, BigInt, BigInt)> {
+    let f = GaloisField::GaloisField(n);
+    let c = |v: u32| f.new(&BigInt::from(v));
+    let (_, coefficients) = HILBERT.iter().find(|(e, _)| *e == d)?;
+    let mut h: Poly = coefficients
+        .iter()
+        .map(|s| f.new(&BigInt::parse_bytes(s.as_bytes(), 10).unwrap()))
+        .collect();
+    h.push(f.one());
+    let j = root(h, n)?;
+
+    // j = 0 and j = 1728 have six and four twists, by a non-residue that is
+    // also a non-cube for j = 0.
+    let (a, b, twists) = if j == c(0) {
+        (c(0), c(1), 6)
+    } else if j == c(1728) {
+        (c(1), c(0), 4)
+    } else {
+        let k = (j.clone() / (c(1728) - j))?;
+        (k.clone() * c(3), k * c(2), 2)
+    };
+    let n1: BigInt = n - 1u32;
+    let minus_one = f.new(&n1);
+    let g = (2u32..1000).map(c).find(|g| {
+        g.pow(&(&n1 / 2u32)) == Some(minus_one.clone())
+            && (twists != 6 || g.pow(&(&n1 / 3u32)) != Some(f.one()))
+    })?;
+
+    let k = m / q;
+    for i in 0..twists {
+        let (a, b) = match twists {
+            6 => (a.clone(), b.clone() * g.pow(&BigInt::from(i))?),
+            4 => (a.clone() * g.pow(&BigInt::from(i))?, b.clone()),
+            _ => (
+                a.clone() * g.pow(&BigInt::from(2 * i))?,
+                b.clone() * g.pow(&BigInt::from(3 * i))?,
+            ),
+        };
+        let e = EllipticCurve::new(&f, &a.value, &b.value);
+        for x in (0..POINTS).map(c) {
+            let rhs = x.clone() * x.clone() * x.clone() + a.clone() * x.clone() + b.clone();
+            let Some(y) = rhs.sqrt().filter(|y| !y.value.is_zero()) else {
+                continue;
+            };
+            let p = e.new_point(&x.value, &y.value);
+            let u = scalar_mul_for_factorization(k.clone(), p).ok()?;
+            if u == e.o() {
+                continue;
+            }
+            if scalar_mul_for_factorization(q.clone(), u).ok()? == e.o() {
+                return Some((a.value, b.value, x.value, y.value));
+            }
+            // Some other order: try the next twist.
+            break;
+        }
+    }
+    None
+}
+
+// Polynomials over F_n, constant term first, without trailing zeros.
+type Poly = Vec<GaloisField>;
+
+fn trim(mut a: Poly) -> Poly {
+    while a.last().is_some_and(|c| c.value.is_zero()) {
+        a.pop();
+    }
+    a
+}
+
+fn rem(a: Poly, b: &Poly) -> Option<Poly> {
+    let mut a = trim(a);
+    let lead = b.last()?.inv()?;
+    while a.len() >= b.len() {
+        let c = a.last().unwrap().clone() * lead.clone();
+        let shift = a.len() - b.len();
+        for (i, bi) in b.iter().enumerate() {
+            a[shift + i] = a[shift + i].clone() - c.clone() * bi.clone();
+        }
+        a = trim(a);
+    }
+    Some(a)
+}
+
+fn mul_mod(a: &Poly, b: &Poly, f: &Poly) -> Option<Poly> {
+    if a.is_empty() || b.is_empty() {
+        return Some(Vec::new());
+    }
+    let mut c = vec![f[0].zero(); a.len() + b.len() - 1];
+    for (i, ai) in a.iter().enumerate() {
+        for (j, bj) in b.iter().enumerate() {
+            c[i + j] = c[i + j].clone() + ai.clone() * bj.clone();
+        }
+    }
+    rem(c, f)
+}
+
+fn pow_mod(a: &Poly, e: &BigInt, f: &Poly) -> Option<Poly> {
+    let mut s = vec![f[0].one()];
+    for i in (0..e.bits()).rev() {
+        s = mul_mod(&s, &s, f)?;
+        if e.bit(i) {
+            s = mul_mod(&s, a, f)?;
+        }
+    }
+    Some(s)
+}
+
+fn gcd(a: Poly, b: Poly) -> Option<Poly> {
+    let (mut a, mut b) = (trim(a), trim(b));
+    while !b.is_empty() {
+        let r = rem(a, &b)?;
+        a = b;
+        b = r;
+    }
+    Some(a)
+}
+
+// A root of h modulo the prime n: gcd((x + r)^((n-1)/2) - 1, h) keeps the
+// roots z with z + r a nonzero square, so a few r cut h down to a linear
+// factor.
+fn root(mut h: Poly, n: &BigInt) -> Option<GaloisField> {
+    let f = GaloisField::GaloisField(n);
+    let e: BigInt = (n - 1u32) >> 1u32;
+    for r in 0..ATTEMPTS {
+        if h.len() == 2 {
+            return -h[0].clone() / h[1].clone();
+        }
+        let mut g = pow_mod(&vec![f.new(&BigInt::from(r)), f.one()], &e, &h)?;
+        if g.is_empty() {
+            g.push(f.zero());
+        }
+        g[0] = g[0].clone() - f.one();
+        let g = gcd(h.clone(), g)?;
+        if g.len() > 1 && g.len() < h.len() {
+            h = g;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+    use crate::elliptic_curve::affine_mul;
+
+    #[test]
+    fn test_cornacchia() {
+        // Whatever is found is a representation.
+        let n = BigInt::from(1000003u32);
+        for &(d, _) in HILBERT.iter().take(20) {
+            if let Some((u, v)) = cornacchia(&n, d) {
+                assert_eq!(&u * &u + &v * &v * (-d), &n * 4u32);
+            }
+        }
+        assert_eq!(
+            cornacchia(&BigInt::from(13u32), -4),
+            Some((BigInt::from(6), BigInt::from(2)))
+        );
+    }
+
+    #[test]
+    fn test_root() {
+        // H_-23 splits completely modulo 59 = 6^2 + 23 * 1^2.
+        let n = BigInt::from(59u32);
+        let f = GaloisField::GaloisField(&n);
+        let (_, coefficients) = HILBERT.iter().find(|(d, _)| *d == -23).unwrap();
+        let mut h: Poly = coefficients
+            .iter()
+            .map(|s| f.new(&BigInt::parse_bytes(s.as_bytes(), 10).unwrap()))
+            .collect();
+        h.push(f.one());
+        let j = root(h.clone(), &n).unwrap();
+        let value = h
+            .iter()
+            .rev()
+            .fold(f.zero(), |s, c| s * j.clone() + c.clone());
+        assert_eq!(value, f.zero());
+    }
+
+    #[test]
+    fn test_ecpp() {
+        let n = bi!("100000000000000000000000000000000000000000000000151", 10);
+        let orders = orders(&n);
+        assert!(!orders.is_empty());
+        let order = &orders[0];
+        assert!(large_enough(&order.q, &n));
+        let (a, b, x, y) = curve(&n, order.d, &order.m, &order.q).unwrap();
+
+        let f = GaloisField::GaloisField(&n);
+        let e = EllipticCurve::new(&f, &a, &b);
+        let p = e.new_point(&x, &y);
+        assert_eq!(affine_mul(&order.m, &p), Some(e.o()));
+        assert_ne!(affine_mul(&(&order.m / &order.q), &p), Some(e.o()));
+    }
+}
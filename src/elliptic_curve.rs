@@ -1,9 +1,9 @@
 use crate::galois_field::GaloisField;
-use num::bigint::{BigInt};
+use num::bigint::BigInt;
 use num::Integer;
-use num_traits::{One, Zero};
+use num_traits::Zero;
 use std::fmt::{Display, Formatter, Result};
-use std::ops::{Add, Div, Fn, FnMut, FnOnce, Mul, Neg, Sub};
+use std::ops::{Add, Mul};
 
 #[derive(Debug, Clone)]
 pub struct EllipticCurve {
@@ -19,11 +19,16 @@ impl EllipticCurve {
         EllipticCurve { a, b, f }
     }
 
-    pub fn new_point(&self, x: &BigInt, y: &BigInt) -> Point {
+    pub fn contains(&self, x: &BigInt, y: &BigInt) -> bool {
         let (x, y) = (self.f.new(x), self.f.new(y));
         let lhs = x.pow(&BigInt::from(3u32)).unwrap() + x.clone() * self.a.clone() + self.b.clone();
         let rhs = y.pow(&BigInt::from(2u32)).unwrap();
-        assert_eq!(lhs, rhs);
+        lhs == rhs
+    }
+
+    pub fn new_point(&self, x: &BigInt, y: &BigInt) -> Point {
+        assert!(self.contains(x, y));
+        let (x, y) = (self.f.new(x), self.f.new(y));
         Point {
             x,
             y,
@@ -67,7 +72,7 @@ pub struct Point {
 impl Point {
     pub fn xy(&self) -> (BigInt, BigInt) {
         if self.z.clone() == self.curve.f.zero() {
-            return (BigInt::zero(), BigInt::zero()); // TODO: Should be changed to an appropriate value?
+            (BigInt::zero(), BigInt::zero()) // TODO: Should be changed to an appropriate value?
         } else {
             (
                 (self.x.clone() / self.z.clone().pow(&BigInt::from(2u32)).unwrap())
@@ -111,42 +116,66 @@ impl PartialEq for Point {
 impl Add for Point {
     type Output = Option<Self>;
     fn add(self, rhs: Self) -> Self::Output {
-        jacobian_add(self, rhs)
+        jacobian_add(self, rhs).ok()
         //   affine_add(self, rhs)
     }
 }
 
+// Affine addition that gives up, rather than pick a case, when x1 = x2 but
+// y1 != +-y2 or a denominator is not invertible. Modulo a composite n every
+// result it does return is also the right one modulo each prime factor of n,
+// which is what primality certificates rely on.
 fn affine_add(p1: Point, p2: Point) -> Option<Point> {
-    assert_eq!(p1.curve.a, p2.curve.a);
-    assert_eq!(p1.curve.b, p2.curve.b);
-    assert_eq!(p1.curve.f, p2.curve.f);
+    assert_eq!(p1.curve, p2.curve);
 
-    let p = p1.curve.f.p.clone();
-    let f = GaloisField::GaloisField(&p);
-    if p1 == p1.curve.o() {
+    let o = p1.curve.o();
+    if p1 == o {
         return Some(p2);
     }
-    if p2 == p1.curve.o() {
+    if p2 == o {
         return Some(p1);
     }
-
+    let f = p1.curve.f.clone();
     let (x1, x2) = (p1.x, p2.x);
     let (y1, y2) = (p1.y, p2.y);
 
-    let lambda = ((y2 - y1.clone()) / (x2.clone() - x1.clone()))?;
-    let x3 = lambda.pow(&BigInt::from(2u64))? - x1.clone() - x2;
+    let lambda = if x1 == x2 {
+        if y1.clone() + y2.clone() == f.zero() {
+            return Some(o);
+        }
+        if y1 != y2 {
+            return None;
+        }
+        let num = x1.clone() * x1.clone() * f.new(&BigInt::from(3u32)) + p1.curve.a.clone();
+        (num / (y1.clone() * f.new(&BigInt::from(2u32))))?
+    } else {
+        ((y2 - y1.clone()) / (x2.clone() - x1.clone()))?
+    };
+    let x3 = lambda.clone() * lambda.clone() - x1.clone() - x2;
     let y3 = lambda * (x1 - x3.clone()) - y1;
 
     Some(Point {
         x: x3,
         y: y3,
-        z: f.new(&BigInt::one()),
+        z: f.one(),
         curve: p1.curve,
         n: p1.n,
     })
 }
 
-fn jacobian_add(p1: Point, p2: Point) -> Option<Point> {
+// k * P by double-and-add on `affine_add`, None where that gives up.
+pub fn affine_mul(k: &BigInt, p: &Point) -> Option<Point> {
+    let mut q = p.curve.o();
+    for i in (0..k.bits()).rev() {
+        q = affine_add(q.clone(), q)?;
+        if k.bit(i) {
+            q = affine_add(q, p.clone())?;
+        }
+    }
+    Some(q)
+}
+
+fn jacobian_add(p1: Point, p2: Point) -> std::result::Result<Point, BigInt> {
     assert_eq!(p1.curve.a, p2.curve.a);
     assert_eq!(p1.curve.b, p2.curve.b);
     assert_eq!(p1.curve.f.p, p2.curve.f.p);
@@ -154,128 +183,402 @@ fn jacobian_add(p1: Point, p2: Point) -> Option<Point> {
     let p = p1.curve.f.p.clone();
     let f = GaloisField::GaloisField(&p);
     if p1 == p1.curve.o() {
-        return Some(p2);
+        return Ok(p2);
     }
     if p2 == p1.curve.o() {
-        return Some(p1);
+        return Ok(p1);
     }
-    let (x1, x2) = (p1.x, p2.x);
-    let (y1, y2) = (p1.y, p2.y);
-    let (z1, z2) = (p1.z, p2.z);
+    let (x1, x2) = (p1.x.clone(), p2.x);
+    let (y1, y2) = (p1.y.clone(), p2.y);
+    let (z1, z2) = (p1.z.clone(), p2.z);
 
-    let (u1, u2) = (
-        x1 * z2.pow(&BigInt::from(2u32))?,
-        x2 * z1.pow(&BigInt::from(2u32))?,
-    );
-    let (s1, s2) = (
-        y1.clone() * z2.pow(&BigInt::from(3u32))?,
-        y2.clone() * z1.pow(&BigInt::from(3u32))?,
-    );
+    let (z1z1, z2z2) = (z1.clone() * z1.clone(), z2.clone() * z2.clone());
+    let (u1, u2) = (x1 * z2z2.clone(), x2 * z1z1.clone());
+    let (s1, s2) = (y1 * z2.clone() * z2z2, y2 * z1.clone() * z1z1);
     let h = u2.clone() - u1.clone();
     let r = (s2.clone() - s1.clone()) * f.new(&BigInt::from(2u32));
-    let i = h.pow(&BigInt::from(2u32))? * f.new(&BigInt::from(4u32));
+    if h == f.zero() {
+        return if r == f.zero() {
+            double(p1)
+        } else {
+            Ok(p1.curve.o())
+        };
+    }
+    let i = h.clone() * h.clone() * f.new(&BigInt::from(4u32));
     let (j1, j2) = (i.clone() * h.clone(), i.clone() * u1.clone());
-    let x3 = r.pow(&BigInt::from(2u32))? - j1.clone() - j2.clone() * f.new(&BigInt::from(2u32));
+    let x3 = r.clone() * r.clone() - j1.clone() - j2.clone() * f.new(&BigInt::from(2u32));
     let y3 = (j2.clone() - x3.clone()) * r.clone()
         - s1.clone() * j1.clone() * f.new(&BigInt::from(2u32));
     let z3 = h.clone() * z1.clone() * z2 * f.new(&BigInt::from(2u32));
 
-    let finity: bool = z3.clone() != p1.curve.f.zero();
-    if finity {
-        let x3 = (x3.clone() / z3.clone().pow(&BigInt::from(2u32))?)?;
-        let y3 = (y3.clone() / z3.clone().pow(&BigInt::from(3u32))?)?;
-        let z3 = p1.curve.f.new(&BigInt::from(finity as i32));
-        Some(Point {
-            x: x3,
-            y: y3,
-            z: z3,
-            curve: p1.curve,
-            n: p1.n,
-        })
-    } else {
-        Some(p1.curve.o())
+    normalize(x3, y3, z3, p1.curve, p1.n)
+}
+
+// Brings a Jacobian point back to z = 1. A non-invertible z3 is reported as
+// Err(gcd(z3, p)), which is exactly the divisor ECM is looking for.
+fn normalize(
+    x3: GaloisField,
+    y3: GaloisField,
+    z3: GaloisField,
+    curve: EllipticCurve,
+    n: GaloisField,
+) -> std::result::Result<Point, BigInt> {
+    if z3 == curve.f.zero() {
+        return Ok(curve.o());
     }
+    let zinv = z3.try_inv()?;
+    let zinv2 = zinv.clone() * zinv.clone();
+    Ok(Point {
+        x: x3 * zinv2.clone(),
+        y: y3 * zinv2 * zinv,
+        z: curve.f.one(),
+        curve,
+        n,
+    })
 }
 
 impl Mul<Point> for BigInt {
     type Output = Option<Point>;
     fn mul(self, rhs: Point) -> Self::Output {
-        scalar_mul(self, rhs)
+        scalar_mul_for_factorization(self, rhs).ok()
     }
 }
 
-fn scalar_mul(k: BigInt, p: Point) -> Option<Point> {
-    let mut p0: Point = p.clone();
-    let mut p: Point = p.curve.o();
-    let mut k: BigInt = k.clone();
-
-    while k > BigInt::zero() {
-        if k.clone() & BigInt::one() == BigInt::one() {
-            p = (p + p0.clone())?;
-        }
-        p0 = double(p0.clone())?;
-        k >>= 1u32;
-    }
-    Some(p)
+pub fn add_for_factorization(p1: Point, p2: Point) -> std::result::Result<Point, BigInt> {
+    jacobian_add(p1, p2)
 }
 
-pub fn scalar_mul_for_factorization(k: BigInt, p: Point) -> Option<BigInt> {
+// Double-and-add scalar multiplication. Fails with the gcd of the modulus and
+// the first denominator that could not be inverted.
+pub fn scalar_mul_for_factorization(k: BigInt, p: Point) -> std::result::Result<Point, BigInt> {
     let mut p0: Point = p.clone();
-    let p: Point = p.curve.o();
+    let mut p: Point = p.curve.o();
     let mut k: BigInt = k.clone();
 
     while k > BigInt::zero() {
-        if k.clone() & BigInt::one() == BigInt::one() {
-            let q = p.clone() + p0.clone();
-            match q {
-                Some(_) => return None,
-                None => {
-                    return Some(p.curve.f.p.gcd(&(p.x - p0.x).value));
-                }
-            };
+        if k.is_odd() {
+            p = jacobian_add(p, p0.clone())?;
         }
-        p0 = double(p0.clone())?;
         k >>= 1u32;
+        if k > BigInt::zero() {
+            p0 = double(p0)?;
+        }
     }
-    None
+    Ok(p)
 }
 
-fn double(pp: Point) -> Option<Point> {
+fn double(pp: Point) -> std::result::Result<Point, BigInt> {
     let (a, p) = (pp.curve.a.clone(), pp.curve.f.p.clone());
     let f = GaloisField::GaloisField(&p);
     let (x1, y1, z1) = (pp.x, pp.y, pp.z);
-    let s = ((x1.clone() + y1.pow(&BigInt::from(2u32))?).pow(&BigInt::from(2u32))?
-        - x1.pow(&BigInt::from(2u32))?
-        - y1.pow(&BigInt::from(4u32))?)
+    let (xx, yy, zz) = (
+        x1.clone() * x1.clone(),
+        y1.clone() * y1.clone(),
+        z1.clone() * z1.clone(),
+    );
+    let yyyy = yy.clone() * yy.clone();
+    let s = ((x1.clone() + yy.clone()) * (x1 + yy) - xx.clone() - yyyy.clone())
         * f.new(&BigInt::from(2u32));
-    let m = (x1.pow(&BigInt::from(2u32))?) * f.new(&BigInt::from(3u32))
-        + (z1.pow(&BigInt::from(4u32))?) * a;
-    let x3 = m.pow(&BigInt::from(2u32))? - s.clone() * f.new(&BigInt::from(2u32));
-    let y3 =
-        (s.clone() - x3.clone()) * m - (y1.pow(&BigInt::from(4u32))?) * f.new(&BigInt::from(8u32));
+    let m = xx * f.new(&BigInt::from(3u32)) + zz.clone() * zz * a;
+    let x3 = m.clone() * m.clone() - s.clone() * f.new(&BigInt::from(2u32));
+    let y3 = (s.clone() - x3.clone()) * m - yyyy * f.new(&BigInt::from(8u32));
     let z3 = y1 * z1 * f.new(&BigInt::from(2u32));
 
-    let finity: bool = z3.clone() != pp.curve.f.zero();
-    if finity {
-        let x3 = (x3.clone() / z3.clone().pow(&BigInt::from(2u32))?)?;
-        let y3 = (y3.clone() / z3.clone().pow(&BigInt::from(3u32))?)?;
-        let z3 = pp.curve.f.new(&BigInt::from(finity as i32));
-        Some(Point {
-            x: x3,
-            y: y3,
-            z: z3,
-            curve: pp.curve,
-            n: pp.n,
-        })
-    } else {
-        Some(pp.curve.o())
+    normalize(x3, y3, z3, pp.curve, pp.n)
+}
+
+// Montgomery curve By^2 = x^3 + Ax^2 + x, kept as a24 = (A + 2) / 4 since
+// the x-only formulas never need A or B themselves.
+#[derive(Debug, Clone)]
+pub struct MontgomeryCurve {
+    a24: GaloisField,
+    f: GaloisField,
+}
+
+// Projective (X : Z) point on a Montgomery curve; y is never computed.
+#[derive(Debug, Clone)]
+pub struct MontgomeryPoint {
+    pub x: GaloisField,
+    pub z: GaloisField,
+}
+
+impl MontgomeryCurve {
+    // Suyama's parametrization: u = sigma^2 - 5, v = 4 sigma,
+    // (A + 2) / 4 = (v - u)^3 (3u + v) / (16 u^3 v), P = (u^3 : v^3).
+    // The group order is divisible by 12. Fails with gcd(16 u^3 v, p) when
+    // the denominator is not invertible.
+    pub fn suyama(
+        f: &GaloisField,
+        sigma: &BigInt,
+    ) -> std::result::Result<(MontgomeryCurve, MontgomeryPoint), BigInt> {
+        let s = f.new(sigma);
+        let u = s.clone() * s.clone() - f.new(&BigInt::from(5u32));
+        let v = s * f.new(&BigInt::from(4u32));
+        let u3 = u.clone() * u.clone() * u.clone();
+        let v3 = v.clone() * v.clone() * v.clone();
+        let vu = v.clone() - u.clone();
+        let num = vu.clone() * vu.clone() * vu * (u * f.new(&BigInt::from(3u32)) + v.clone());
+        let den = u3.clone() * v * f.new(&BigInt::from(16u32));
+        let a24 = num * den.try_inv()?;
+        Ok((
+            MontgomeryCurve { a24, f: f.clone() },
+            MontgomeryPoint { x: u3, z: v3 },
+        ))
+    }
+
+    pub fn double(&self, p: &MontgomeryPoint) -> MontgomeryPoint {
+        let s = p.x.clone() + p.z.clone();
+        let d = p.x.clone() - p.z.clone();
+        let (ss, dd) = (s.clone() * s, d.clone() * d);
+        let t = ss.clone() - dd.clone();
+        MontgomeryPoint {
+            x: ss * dd.clone(),
+            z: t.clone() * (dd + self.a24.clone() * t),
+        }
+    }
+
+    // P + Q given their difference P - Q.
+    pub fn add(
+        &self,
+        p: &MontgomeryPoint,
+        q: &MontgomeryPoint,
+        diff: &MontgomeryPoint,
+    ) -> MontgomeryPoint {
+        let u = (p.x.clone() - p.z.clone()) * (q.x.clone() + q.z.clone());
+        let v = (p.x.clone() + p.z.clone()) * (q.x.clone() - q.z.clone());
+        let (s, d) = (u.clone() + v.clone(), u - v);
+        MontgomeryPoint {
+            x: diff.z.clone() * s.clone() * s,
+            z: diff.x.clone() * d.clone() * d,
+        }
+    }
+
+    // Montgomery ladder: k * P using one doubling and one differential
+    // addition per bit of k.
+    pub fn ladder(&self, k: &BigInt, p: &MontgomeryPoint) -> MontgomeryPoint {
+        if k.is_zero() {
+            return MontgomeryPoint {
+                x: self.f.one(),
+                z: self.f.zero(),
+            };
+        }
+        let (mut r0, mut r1) = (p.clone(), self.double(p));
+        for i in (0..k.bits() - 1).rev() {
+            if k.bit(i) {
+                r0 = self.add(&r1, &r0, p);
+                r1 = self.double(&r1);
+            } else {
+                r1 = self.add(&r0, &r1, p);
+                r0 = self.double(&r0);
+            }
+        }
+        r0
+    }
+}
+
+impl MontgomeryPoint {
+    // Projective x-coordinates are equal up to sign of the point.
+    #[cfg(test)]
+    pub fn same_x(&self, rhs: &Self) -> bool {
+        self.x.clone() * rhs.z.clone() == rhs.x.clone() * self.z.clone()
+    }
+}
+
+// Twisted Edwards curve ax^2 + y^2 = 1 + dx^2y^2.
+#[derive(Debug, Clone)]
+pub struct EdwardsCurve {
+    a: GaloisField,
+    d: GaloisField,
+    f: GaloisField,
+}
+
+// Extended coordinates (X : Y : Z : T) with x = X/Z, y = Y/Z and T = XY/Z.
+#[derive(Debug, Clone)]
+pub struct EdwardsPoint {
+    pub x: GaloisField,
+    pub y: GaloisField,
+    pub z: GaloisField,
+    pub t: GaloisField,
+}
+
+impl EdwardsCurve {
+    // Curve with rational torsion Z/12 from Montgomery's parametrization:
+    // (u, v) = k * (-2, 4) on v^2 = u^3 - 12u, t = v / 2u,
+    // a = (t^2 - 1) / (t^2 + 3), A = (-3a^4 - 6a^2 + 1) / 4a^3 and
+    // x0 = (3a^2 + 1) / 4a on the Montgomery curve By^2 = x^3 + Ax^2 + x.
+    // Choosing B = x0^3 + Ax0^2 + x0 puts (x0, 1) on it, and the usual map
+    // to twisted Edwards form gives a = (A + 2) / B, d = (A - 2) / B and the
+    // point (x0, (x0 - 1) / (x0 + 1)). Fails with the gcd of a denominator
+    // that is not invertible.
+    pub fn z12(
+        f: &GaloisField,
+        k: &BigInt,
+    ) -> std::result::Result<(EdwardsCurve, EdwardsPoint), BigInt> {
+        let c = |v: i32| f.new(&BigInt::from(v));
+        let e = EllipticCurve::new(f, &BigInt::from(-12), &BigInt::zero());
+        let g = e.new_point(&BigInt::from(-2), &BigInt::from(4));
+        let q = scalar_mul_for_factorization(k.clone(), g)?;
+        let (u, v) = (q.x.clone(), q.y.clone());
+
+        let t = v * (u * c(2)).try_inv()?;
+        let t2 = t.clone() * t;
+        let a = (t2.clone() - c(1)) * (t2 + c(3)).try_inv()?;
+        let a2 = a.clone() * a.clone();
+        let ainv = (a.clone() * c(4)).try_inv()?;
+        let big_a = (c(1) - a2.clone() * a2.clone() * c(3) - a2.clone() * c(6))
+            * ainv.clone()
+            * (a2.clone()).try_inv()?;
+        let x0 = (a2 * c(3) + c(1)) * ainv;
+        let b = (x0.clone() * x0.clone() + big_a.clone() * x0.clone() + c(1)) * x0.clone();
+
+        let binv = b.try_inv()?;
+        let curve = EdwardsCurve {
+            a: (big_a.clone() + c(2)) * binv.clone(),
+            d: (big_a - c(2)) * binv,
+            f: f.clone(),
+        };
+        let y0 = (x0.clone() - c(1)) * (x0.clone() + c(1)).try_inv()?;
+        let p = EdwardsPoint {
+            t: x0.clone() * y0.clone(),
+            x: x0,
+            y: y0,
+            z: f.one(),
+        };
+        Ok((curve, p))
+    }
+
+    // Curve with rational torsion Z/2 x Z/8 from Atkin and Morain's family, as
+    // given by Bernstein, Birkner, Lange and Peters: (s, t) = k * (12, 40) on
+    // t^2 = s^3 - 8s - 32, alpha = 1 / ((t + 25) / (s - 9) + 1),
+    // beta = 2 alpha (4 alpha + 1) / (8 alpha^2 - 1), x8 = 2 beta - 1 and
+    // d = (2 x8^2 - 1) / x8^4 on x^2 + y^2 = 1 + d x^2 y^2, where (x8, x8) has
+    // order 8 and d is a square. The point of infinite order is
+    // x = x8 (4 beta - 3) / (6 beta - 5),
+    // y = x8 (t^2 + 50t - 2s^3 + 27s^2 - 104) / ((t + 3s - 2)(t + s + 16)).
+    // Fails with the gcd of a denominator that is not invertible.
+    pub fn z2z8(
+        f: &GaloisField,
+        k: &BigInt,
+    ) -> std::result::Result<(EdwardsCurve, EdwardsPoint), BigInt> {
+        let c = |v: i32| f.new(&BigInt::from(v));
+        let e = EllipticCurve::new(f, &BigInt::from(-8), &BigInt::from(-32));
+        let g = e.new_point(&BigInt::from(12), &BigInt::from(40));
+        let q = scalar_mul_for_factorization(k.clone(), g)?;
+        let (s, t) = (q.x.clone(), q.y.clone());
+
+        let alpha = ((t.clone() + c(25)) * (s.clone() - c(9)).try_inv()? + c(1)).try_inv()?;
+        let beta = alpha.clone()
+            * (alpha.clone() * c(4) + c(1))
+            * c(2)
+            * (alpha.clone() * alpha * c(8) - c(1)).try_inv()?;
+        let x8 = beta.clone() * c(2) - c(1);
+        let x8_2 = x8.clone() * x8.clone();
+        let d = (x8_2.clone() * c(2) - c(1)) * (x8_2.clone() * x8_2).try_inv()?;
+
+        let x0 = x8.clone() * (beta.clone() * c(4) - c(3)) * (beta * c(6) - c(5)).try_inv()?;
+        let s2 = s.clone() * s.clone();
+        let num = t.clone() * t.clone() + t.clone() * c(50) - s2.clone() * s.clone() * c(2)
+            + s2 * c(27)
+            - c(104);
+        let den = (t.clone() + s.clone() * c(3) - c(2)) * (t + s + c(16));
+        let y0 = x8 * num * den.try_inv()?;
+
+        let curve = EdwardsCurve {
+            a: f.one(),
+            d,
+            f: f.clone(),
+        };
+        let p = EdwardsPoint {
+            t: x0.clone() * y0.clone(),
+            x: x0,
+            y: y0,
+            z: f.one(),
+        };
+        Ok((curve, p))
+    }
+
+    pub fn identity(&self) -> EdwardsPoint {
+        EdwardsPoint {
+            x: self.f.zero(),
+            y: self.f.one(),
+            z: self.f.one(),
+            t: self.f.zero(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn is_on_curve(&self, p: &EdwardsPoint) -> bool {
+        let (xx, yy, zz) = (
+            p.x.clone() * p.x.clone(),
+            p.y.clone() * p.y.clone(),
+            p.z.clone() * p.z.clone(),
+        );
+        let lhs = (self.a.clone() * xx.clone() + yy.clone()) * zz.clone();
+        let rhs = zz.clone() * zz + self.d.clone() * xx * yy;
+        lhs == rhs && p.x.clone() * p.y.clone() == p.t.clone() * p.z.clone()
+    }
+
+    // Unified addition (Hisil-Wong-Carter-Dawson 2008), 9M without inversions.
+    pub fn add(&self, p: &EdwardsPoint, q: &EdwardsPoint) -> EdwardsPoint {
+        let a = p.x.clone() * q.x.clone();
+        let b = p.y.clone() * q.y.clone();
+        let c = p.t.clone() * self.d.clone() * q.t.clone();
+        let d = p.z.clone() * q.z.clone();
+        let e = (p.x.clone() + p.y.clone()) * (q.x.clone() + q.y.clone()) - a.clone() - b.clone();
+        let f = d.clone() - c.clone();
+        let g = d + c;
+        let h = b - self.a.clone() * a;
+        EdwardsPoint {
+            x: e.clone() * f.clone(),
+            y: g.clone() * h.clone(),
+            z: f * g,
+            t: e * h,
+        }
+    }
+
+    pub fn double(&self, p: &EdwardsPoint) -> EdwardsPoint {
+        let a = p.x.clone() * p.x.clone();
+        let b = p.y.clone() * p.y.clone();
+        let c = p.z.clone() * p.z.clone() * self.f.new(&BigInt::from(2u32));
+        let d = self.a.clone() * a.clone();
+        let e = (p.x.clone() + p.y.clone()) * (p.x.clone() + p.y.clone()) - a - b.clone();
+        let g = d.clone() + b.clone();
+        let f = g.clone() - c;
+        let h = d - b;
+        EdwardsPoint {
+            x: e.clone() * f.clone(),
+            y: g.clone() * h.clone(),
+            z: f * g,
+            t: e * h,
+        }
+    }
+
+    pub fn mul(&self, k: &BigInt, p: &EdwardsPoint) -> EdwardsPoint {
+        let mut q = self.identity();
+        for i in (0..k.bits()).rev() {
+            q = self.double(&q);
+            if k.bit(i) {
+                q = self.add(&q, p);
+            }
+        }
+        q
+    }
+}
+
+impl EdwardsPoint {
+    // Projective y-coordinates are equal up to sign of the point.
+    #[cfg(test)]
+    pub fn same_y(&self, rhs: &Self) -> bool {
+        self.y.clone() * rhs.z.clone() == rhs.y.clone() * self.z.clone()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bi;
     use num::bigint::BigInt;
+    use num::ToPrimitive;
     #[test]
 
     fn test_elliptic_curve() {
@@ -323,15 +626,15 @@ mod tests {
             16
         );
 
-        let P = e.new_point(&x1, &y1);
-        let Q = e.new_point(&x2, &y2);
-        let R = e.new_point(&x3, &y3);
-        assert_eq!((P.clone() + Q.clone()).unwrap(), R);
+        let p = e.new_point(&x1, &y1);
+        let q = e.new_point(&x2, &y2);
+        let r = e.new_point(&x3, &y3);
+        assert_eq!((p.clone() + q.clone()).unwrap(), r);
 
-        assert_eq!((Q.clone() + Q.curve.o()).unwrap(), Q.clone());
-        assert_eq!((Q.curve.o() + Q.clone()).unwrap(), Q.clone());
+        assert_eq!((q.clone() + q.curve.o()).unwrap(), q.clone());
+        assert_eq!((q.curve.o() + q.clone()).unwrap(), q.clone());
 
-        assert_eq!((BigInt::from(2u32) * Q.curve.o()).unwrap(), Q.curve.o());
+        assert_eq!((BigInt::from(2u32) * q.curve.o()).unwrap(), q.curve.o());
 
         let x3 = bi!(
             "ff339cace0a8015a7f693252f3f810e6f04a427d1b0cef16020325be952166c1",
@@ -341,7 +644,79 @@ mod tests {
             "1a8e4912c5b1f36c475cd375ec3ac797a1fc491d4f7584ffd1234d301205d00e",
             16
         );
-        let R = e.new_point(&x3, &y3);
-        assert_eq!((k * P.clone()).unwrap(), R);
+        let r = e.new_point(&x3, &y3);
+        assert_eq!((k * p.clone()).unwrap(), r);
+    }
+
+    #[test]
+    fn test_montgomery_ladder() {
+        let p = bi!("1000000007", 10);
+        let f = GaloisField::GaloisField(&p);
+        let (e, g) = MontgomeryCurve::suyama(&f, &BigInt::from(17u32)).unwrap();
+
+        let g7 = e.ladder(&BigInt::from(7u32), &g);
+        let g42 = e.ladder(&BigInt::from(42u32), &g);
+        assert!(e.ladder(&BigInt::from(6u32), &g7).same_x(&g42));
+
+        let g2 = e.double(&g);
+        let g3 = e.add(&g2, &g, &g);
+        assert!(e.ladder(&BigInt::from(3u32), &g).same_x(&g3));
+        assert!(e.add(&g3, &g2, &g).same_x(&e.ladder(&BigInt::from(5u32), &g)));
+    }
+
+    #[test]
+    fn test_edwards_z12() {
+        let p = bi!("1000000007", 10);
+        let f = GaloisField::GaloisField(&p);
+        let (e, g) = EdwardsCurve::z12(&f, &BigInt::from(5u32)).unwrap();
+        assert!(e.is_on_curve(&g));
+
+        let g7 = e.mul(&BigInt::from(7u32), &g);
+        let g42 = e.mul(&BigInt::from(42u32), &g);
+        assert!(e.is_on_curve(&g42));
+        assert!(e.mul(&BigInt::from(6u32), &g7).same_y(&g42));
+        assert!(e.add(&g7, &e.double(&g7)).same_y(&e.mul(&BigInt::from(21u32), &g)));
+    }
+
+    #[test]
+    fn test_edwards_z2z8() {
+        let p = 10007u64;
+        let f = GaloisField::GaloisField(&BigInt::from(p));
+        let (e, g) = EdwardsCurve::z2z8(&f, &BigInt::from(3u32)).unwrap();
+        assert!(e.is_on_curve(&g));
+
+        // Counts the points: two y for each x where (1 - x^2) / (1 - d x^2)
+        // is a nonzero square, one where it vanishes, and the four points at
+        // infinity of a curve with d a square.
+        let pow = |mut b: u64, mut k: u64| {
+            let mut r = 1;
+            while k > 0 {
+                if k & 1 == 1 {
+                    r = r * b % p;
+                }
+                b = b * b % p;
+                k >>= 1;
+            }
+            r
+        };
+        let d = e.d.value.to_u64().unwrap();
+        assert_eq!(pow(d, (p - 1) / 2), 1);
+        let mut order = 4;
+        for x in 0..p {
+            let x2 = x * x % p;
+            let den = (1 + p - d * x2 % p) % p;
+            if den == 0 {
+                continue;
+            }
+            let r = (1 + p - x2) % p * pow(den, p - 2) % p;
+            order += match (r, pow(r, (p - 1) / 2)) {
+                (0, _) => 1,
+                (_, 1) => 2,
+                _ => 0,
+            };
+        }
+        assert_eq!(order % 16, 0);
+        let o = e.mul(&BigInt::from(order), &g);
+        assert!(o.x == f.zero() && o.y == o.z);
     }
 }
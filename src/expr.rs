@@ -0,0 +1,318 @@
+use crate::sieve;
+use num::bigint::BigInt;
+use num::{Integer, One, Signed, ToPrimitive, Zero};
+
+// Integers written as expressions, such as 2^512+1, 10!, (3^91-1)/2 or M(127):
+//   + - * /          with exact division, and unary minus
+//   ^                right associative, binding tighter than unary minus
+//   n! n#            factorial and primorial
+//   M(p) F(k)        Mersenne and Fermat numbers 2^p - 1 and 2^2^k + 1
+//   fib(n) lucas(n)  Fibonacci and Lucas numbers
+// Literals are decimal, or hexadecimal, octal and binary after 0x, 0o and 0b.
+
+// Largest argument of !, #, fib and lucas.
+const MAX_ARGUMENT: u64 = 100_000;
+
+// Largest size of a power, in bits.
+const MAX_BITS: u64 = 1 << 24;
+
+pub fn parse(text: &str) -> Result<BigInt, String> {
+    let mut parser = Parser { text, pos: 0 };
+    let n = parser.sum()?;
+    match parser.peek() {
+        None => Ok(n),
+        Some(_) => Err(parser.unexpected()),
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    // The next character after whitespace, which is skipped.
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+        self.text[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn unexpected(&mut self) -> String {
+        match self.peek() {
+            Some(c) => format!("unexpected '{c}' at position {}", self.pos + 1),
+            None => "unexpected end of input".to_string(),
+        }
+    }
+
+    // sum = product (('+' | '-') product)*
+    fn sum(&mut self) -> Result<BigInt, String> {
+        let mut n = self.product()?;
+        loop {
+            if self.eat('+') {
+                n += self.product()?;
+            } else if self.eat('-') {
+                n -= self.product()?;
+            } else {
+                return Ok(n);
+            }
+        }
+    }
+
+    // product = unary (('*' | '/') unary)*
+    fn product(&mut self) -> Result<BigInt, String> {
+        let mut n = self.unary()?;
+        loop {
+            if self.eat('*') {
+                n *= self.unary()?;
+            } else if self.eat('/') {
+                let d = self.unary()?;
+                if d.is_zero() {
+                    return Err("division by zero".to_string());
+                }
+                let (q, r) = n.div_rem(&d);
+                if !r.is_zero() {
+                    return Err(format!("{n} is not divisible by {d}"));
+                }
+                n = q;
+            } else {
+                return Ok(n);
+            }
+        }
+    }
+
+    // unary = '-' unary | power
+    fn unary(&mut self) -> Result<BigInt, String> {
+        if self.eat('-') {
+            return Ok(-self.unary()?);
+        }
+        self.power()
+    }
+
+    // power = postfix ('^' unary)?
+    fn power(&mut self) -> Result<BigInt, String> {
+        let base = self.postfix()?;
+        if !self.eat('^') {
+            return Ok(base);
+        }
+        let e = self.unary()?;
+        if e.is_negative() {
+            return Err(format!("negative exponent {e}"));
+        }
+        // 0, 1 and -1 stay small whatever the exponent.
+        if base.abs() <= BigInt::one() {
+            return Ok(if e.is_zero() || (base.is_negative() && e.is_even()) {
+                BigInt::one()
+            } else {
+                base
+            });
+        }
+        match e.to_u64() {
+            Some(e) if base.bits().saturating_mul(e) <= MAX_BITS => Ok(base.pow(e as u32)),
+            _ => Err(format!("{base}^{e} is too large")),
+        }
+    }
+
+    // postfix = primary ('!' | '#')*
+    fn postfix(&mut self) -> Result<BigInt, String> {
+        let mut n = self.primary()?;
+        loop {
+            if self.eat('!') {
+                n = (1..=argument("factorial", &n)?).map(BigInt::from).product();
+            } else if self.eat('#') {
+                let primes = sieve::primes(argument("primorial", &n)?);
+                n = primes.iter().copied().map(BigInt::from).product();
+            } else {
+                return Ok(n);
+            }
+        }
+    }
+
+    // primary = literal | '(' sum ')' | name '(' sum ')'
+    fn primary(&mut self) -> Result<BigInt, String> {
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let n = self.sum()?;
+                if !self.eat(')') {
+                    return Err(self.unexpected());
+                }
+                Ok(n)
+            }
+            Some(c) if c.is_ascii_digit() => self.literal(),
+            Some(c) if c.is_ascii_alphabetic() => self.call(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn literal(&mut self) -> Result<BigInt, String> {
+        let start = self.pos;
+        let rest = &self.text[start..];
+        let radix = match rest.get(..2).map(str::to_ascii_lowercase).as_deref() {
+            Some("0x") => 16,
+            Some("0o") => 8,
+            Some("0b") => 2,
+            _ => 10,
+        };
+        if radix != 10 {
+            self.pos += 2;
+        }
+        let digits_start = self.pos;
+        while let Some(c) = self.text[self.pos..].chars().next() {
+            if !c.is_ascii_alphanumeric() {
+                break;
+            }
+            if !c.is_digit(radix) {
+                return Err(format!(
+                    "invalid digit '{c}' in base {radix} at position {}",
+                    self.pos + 1
+                ));
+            }
+            self.pos += 1;
+        }
+        let digits = &self.text[digits_start..self.pos];
+        if digits.is_empty() {
+            return Err(format!("missing digits after '{}'", &rest[..2]));
+        }
+        Ok(BigInt::parse_bytes(digits.as_bytes(), radix).unwrap())
+    }
+
+    fn call(&mut self) -> Result<BigInt, String> {
+        let start = self.pos;
+        let rest = &self.text[start..];
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+        let name = &rest[..len];
+        self.pos += len;
+        if !self.eat('(') {
+            return Err(self.unexpected());
+        }
+        let n = self.sum()?;
+        if !self.eat(')') {
+            return Err(self.unexpected());
+        }
+        let two = BigInt::from(2u32);
+        match name {
+            "M" => Ok(two.pow(exponent(&n, MAX_BITS)?) - 1u32),
+            "F" => Ok(two.pow(1 << exponent(&n, MAX_BITS.ilog2() as u64)?) + 1u32),
+            "fib" => Ok(fibonacci(argument(name, &n)?).0),
+            "lucas" => {
+                let (f, f1) = fibonacci(argument(name, &n)?);
+                Ok(f1 * 2u32 - f)
+            }
+            _ => Err(format!(
+                "unknown function '{name}' at position {}",
+                start + 1
+            )),
+        }
+    }
+}
+
+// n as the argument of f, if it is at most MAX_ARGUMENT.
+fn argument(f: &str, n: &BigInt) -> Result<u64, String> {
+    if n.is_negative() {
+        return Err(format!("{f} of negative {n}"));
+    }
+    n.to_u64()
+        .filter(|&n| n <= MAX_ARGUMENT)
+        .ok_or_else(|| format!("{f} of {n} is too large"))
+}
+
+// n as an exponent, if it is at most `max`.
+fn exponent(n: &BigInt, max: u64) -> Result<u32, String> {
+    if n.is_negative() {
+        return Err(format!("negative exponent {n}"));
+    }
+    n.to_u64()
+        .filter(|&n| n <= max)
+        .map(|n| n as u32)
+        .ok_or_else(|| format!("exponent {n} is too large"))
+}
+
+// (F_n, F_n+1) by doubling: F_2k = F_k (2 F_k+1 - F_k) and
+// F_2k+1 = F_k^2 + F_k+1^2.
+fn fibonacci(n: u64) -> (BigInt, BigInt) {
+    let (mut a, mut b) = (BigInt::zero(), BigInt::one());
+    for i in (0..u64::BITS - n.leading_zeros()).rev() {
+        let c = &a * (&b * 2u32 - &a);
+        let d = &a * &a + &b * &b;
+        (a, b) = if n >> i & 1 == 1 {
+            (d.clone(), c + d)
+        } else {
+            (c, d)
+        };
+    }
+    (a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_parse() {
+        let n = |s: &str| parse(s).unwrap();
+        assert_eq!(
+            n("1234567890123456789012345"),
+            bi!("1234567890123456789012345", 10)
+        );
+        assert_eq!(n(" 2^64 + 1 "), BigInt::from(u64::MAX) + 2u32);
+        assert_eq!(n("2^3^2"), BigInt::from(512));
+        assert_eq!(n("-2^2"), BigInt::from(-4));
+        assert_eq!(n("(-2)^3"), BigInt::from(-8));
+        assert_eq!(n("2*3+4*5-6/3"), BigInt::from(24));
+        assert_eq!(n("2-3-4"), BigInt::from(-5));
+        assert_eq!(n("(3^91-1)/2"), (BigInt::from(3).pow(91) - 1u32) / 2u32);
+        assert_eq!(n("10!"), BigInt::from(3628800));
+        assert_eq!(n("3!!"), BigInt::from(720));
+        assert_eq!(n("0!"), BigInt::one());
+        assert_eq!(n("13#"), BigInt::from(30030));
+        assert_eq!(n("2^3!"), BigInt::from(64));
+        assert_eq!(
+            n("0xff + 0o17 + 0b101 + 0XA"),
+            BigInt::from(255 + 15 + 5 + 10)
+        );
+        assert_eq!(
+            n("M(127)"),
+            bi!("170141183460469231731687303715884105727", 10)
+        );
+        assert_eq!(n("F(5)"), BigInt::from(4294967297u64));
+        assert_eq!(n("fib(0) + fib(1)"), BigInt::one());
+        assert_eq!(n("fib(100)"), bi!("354224848179261915075", 10));
+        assert_eq!(n("lucas(0)"), BigInt::from(2));
+        assert_eq!(n("lucas(10)"), BigInt::from(123));
+        assert_eq!(n("1^100000000000 + (-1)^3 + 0^0"), BigInt::one());
+    }
+
+    #[test]
+    fn test_errors() {
+        let e = |s: &str| parse(s).unwrap_err();
+        assert_eq!(e(""), "unexpected end of input");
+        assert_eq!(e("12a"), "invalid digit 'a' in base 10 at position 3");
+        assert_eq!(e("0b102"), "invalid digit '2' in base 2 at position 5");
+        assert_eq!(e("0x"), "missing digits after '0x'");
+        assert_eq!(e("2 + * 3"), "unexpected '*' at position 5");
+        assert_eq!(e("(1 + 2"), "unexpected end of input");
+        assert_eq!(e("1 2"), "unexpected '2' at position 3");
+        assert_eq!(e("foo(3)"), "unknown function 'foo' at position 1");
+        assert_eq!(e("7/2"), "7 is not divisible by 2");
+        assert_eq!(e("1/0"), "division by zero");
+        assert_eq!(e("2^-1"), "negative exponent -1");
+        assert_eq!(e("2^100000000"), "2^100000000 is too large");
+        assert_eq!(e("(-1)!"), "factorial of negative -1");
+        assert_eq!(e("1000000#"), "primorial of 1000000 is too large");
+        assert_eq!(e("F(30)"), "exponent 30 is too large");
+        assert_eq!(e("1 + é"), "unexpected 'é' at position 5");
+    }
+}
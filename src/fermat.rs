@@ -1,7 +1,8 @@
 use crate::algs::is_square;
-use crate::traits::{Factor, Factorizer, Factors};
+use crate::driver;
+use crate::traits::{Factorizer, Factors, Splitter};
 use num::bigint::BigInt;
-use std::collections::HashMap;
+use num::{Integer, One, ToPrimitive};
 
 pub struct Fermat {
     n: BigInt,
@@ -15,28 +16,59 @@ impl Fermat {
 
 impl Factorizer for Fermat {
     fn factorize(&self) -> Option<Factors> {
-        factorize(&self.n)
+        driver::factorize(&self.n, self)
     }
 }
 
-fn factorize(n: &BigInt) -> Option<Factors> {
+impl Splitter for Fermat {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        split(n, u64::MAX)
+    }
+}
+
+pub fn split(n: &BigInt, steps: u64) -> Option<BigInt> {
+    if n.is_even() {
+        return Some(BigInt::from(2));
+    }
+    if let Some(m) = n.to_u128() {
+        return split_native(m, steps).map(BigInt::from);
+    }
     let mut x: BigInt = n.sqrt();
     if x.clone() * x.clone() < n.clone() {
         x += 1u32;
     }
-    let mut y2 = x.clone() * x.clone() - n.clone();
-    while !is_square(&y2) {
+    for _ in 0..steps {
+        let y2 = x.clone() * x.clone() - n;
+        if is_square(&y2) {
+            let d = x.clone() - y2.sqrt();
+            return if d > BigInt::one() { Some(d) } else { None };
+        }
         x += 1u32;
-        y2 = x.clone() * x.clone() - n;
     }
-    let a = x.clone() + y2.sqrt();
-    let b = x.clone() - y2.sqrt();
+    None
+}
 
-    let mut f: HashMap<BigInt, u32> = HashMap::new();
-    f.insert(a, 1);
-    f.insert(b, 1);
-    let res = Factors::new(Some(f));
-    Some(res)
+// The same search on machine words for odd n below 2^128, keeping r = x^2 - n
+// up to date by adding 2x + 1. It gives up if r overflows, which takes some
+// 2^63 steps.
+fn split_native(n: u128, steps: u64) -> Option<u128> {
+    let mut x = n.isqrt();
+    let rem = n - x * x;
+    if rem == 0 {
+        return (x > 1).then_some(x);
+    }
+    let mut r = 2 * x + 1 - rem;
+    x += 1;
+    for _ in 0..steps {
+        let y = r.isqrt();
+        if y * y == r {
+            let d = x - y;
+            return (d > 1).then_some(d);
+        }
+        r = r.checked_add(2 * x + 1)?;
+        x += 1;
+    }
+    None
 }
 
 #[cfg(test)]
@@ -58,5 +90,12 @@ mod tests {
             factors.clone().keys().min().unwrap().clone(),
             bi!("29927402397991286489627837734179186385188296382227", 10)
         );
+
+        // On machine words, up to 2^128.
+        let n = bi!("85070591730234615902737140005361155371", 10);
+        let f = Fermat::new(n).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "9223372036854775783 * 9223372036854775837");
+        let f = Fermat::new(BigInt::from(1000003u64 * 1000003)).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "1000003^2");
     }
 }
@@ -1,11 +1,10 @@
-use crate::bi;
 use num::bigint::{BigInt, Sign};
 use num::Integer;
 use num_traits::{One, Zero};
 use std::fmt::{Debug, Display, Formatter, Result};
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
-#[derive(Clone, Default, Hash)]
+#[derive(Clone, Default, Hash, PartialEq)]
 pub struct GaloisField {
     pub p: BigInt,
     pub value: BigInt,
@@ -19,6 +18,8 @@ impl GaloisField {
         }
     }
 
+    // An element of the same field as self.
+    #[allow(clippy::wrong_self_convention)]
     pub fn new(&self, v: &BigInt) -> Self {
         let p = &self.p;
         if &BigInt::zero() <= v && v < p {
@@ -42,10 +43,6 @@ impl GaloisField {
             },
         }
     }
-    pub fn p(&self) -> BigInt {
-        self.p.clone()
-    }
-
     pub fn zero(&self) -> Self {
         self.new(&BigInt::zero())
     }
@@ -54,17 +51,16 @@ impl GaloisField {
     }
 
     pub fn inv(&self) -> Option<Self> {
+        self.try_inv().ok()
+    }
+
+    // Like `inv`, but reports gcd(value, p) when the inverse does not exist.
+    pub fn try_inv(&self) -> std::result::Result<Self, BigInt> {
         let res = self.value.extended_gcd(&self.p);
-        if self.value == bi!("599", 10) {
-            println!("{self} {res:?}");
-        }
-        if self.value < bi!("1000", 10) {
-            println!("{self}");
-        }
         if res.gcd == BigInt::one() {
-            Some(self.new(&res.x))
+            Ok(self.new(&res.x))
         } else {
-            None
+            Err(res.gcd)
         }
     }
 
@@ -77,12 +73,71 @@ impl GaloisField {
             .inv(),
             _ => Some(Self {
                 p: self.p.clone(),
-                value: self.value.modpow(&e, &self.p),
+                value: self.value.modpow(e, &self.p),
             }),
         }
     }
 }
 
+impl GaloisField {
+    // Tonelli-Shanks: a square root modulo an odd prime p, if there is one.
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.value.is_zero() {
+            return Some(self.clone());
+        }
+        let p1: BigInt = &self.p - 1u32;
+        if self.pow(&(&p1 >> 1u32))? != self.one() {
+            return None;
+        }
+        let s = p1.trailing_zeros()?;
+        let q = &p1 >> s;
+        let minus_one = self.new(&p1);
+        let z = (2u32..)
+            .map(|z| self.new(&BigInt::from(z)))
+            .find(|z| z.pow(&(&p1 >> 1u32)) == Some(minus_one.clone()))?;
+
+        let mut m = s;
+        let mut c = z.pow(&q)?;
+        let mut t = self.pow(&q)?;
+        let mut r = self.pow(&((&q + 1u32) >> 1u32))?;
+        while t != self.one() {
+            let mut i = 1;
+            let mut t2 = t.clone() * t.clone();
+            while t2 != self.one() {
+                if i + 1 >= m {
+                    // Only possible for composite p.
+                    return None;
+                }
+                t2 = t2.clone() * t2;
+                i += 1;
+            }
+            let b = c.pow(&(BigInt::one() << (m - i - 1)))?;
+            m = i;
+            c = b.clone() * b.clone();
+            t = t * c.clone();
+            r = r * b;
+        }
+        Some(r)
+    }
+
+    // Lucas sequence V_k(P, 1) with P = self, via the ladder
+    // V_2j = V_j^2 - 2 and V_2j+1 = V_j * V_j+1 - P.
+    pub fn lucas_v(&self, k: &BigInt) -> Self {
+        let two = self.new(&BigInt::from(2u32));
+        let (mut v0, mut v1) = (two.clone(), self.clone());
+        for i in (0..k.bits()).rev() {
+            if k.bit(i) {
+                v0 = v0 * v1.clone() - self.clone();
+                v1 = v1.clone() * v1 - two.clone();
+            } else {
+                v1 = v0.clone() * v1 - self.clone();
+                v0 = v0.clone() * v0 - two.clone();
+            }
+        }
+        v0
+    }
+}
+
 impl Display for GaloisField {
     fn fmt(&self, f: &mut Formatter) -> Result {
         write!(f, "{}", self.value)
@@ -95,12 +150,6 @@ impl Debug for GaloisField {
     }
 }
 
-impl PartialEq for GaloisField {
-    fn eq(&self, rhs: &Self) -> bool {
-        self.value == rhs.value && self.p == rhs.p
-    }
-}
-
 impl Neg for GaloisField {
     type Output = Self;
     fn neg(self) -> Self::Output {
@@ -145,6 +194,8 @@ impl Mul for GaloisField {
 
 impl Div for GaloisField {
     type Output = Option<Self>;
+    // Division is multiplication by the inverse.
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: Self) -> Self::Output {
         let rinv = rhs.inv()?;
         Some(self * rinv)
@@ -226,6 +277,7 @@ mod tests {
             "ed30030ae80f2255c2acf73d01c3c9a41302928d248c7ceef9e95231b821effe",
             16
         );
+        assert_eq!((x / y).unwrap(), f.new(&z));
 
         let x: GaloisField = f.new(&a);
         let y: &BigInt = &b;
@@ -233,6 +285,7 @@ mod tests {
             "20410ee973c22010125f6a3e8f0b369f9dabb3fb439f4f5478fc91102c5061e3",
             16
         );
+        assert_eq!(x.pow(y).unwrap(), f.new(&z));
 
         let x: GaloisField = f.new(&a);
         let y: &BigInt = &b;
@@ -246,7 +299,7 @@ mod tests {
         let y: GaloisField = f.new(&b);
         let n1: &BigInt = &-BigInt::one();
 
-        assert_eq!(x.pow(&n1), x.inv());
-        assert_eq!(y.pow(&n1), y.inv());
+        assert_eq!(x.pow(n1), x.inv());
+        assert_eq!(y.pow(n1), y.inv());
     }
 }
@@ -0,0 +1,106 @@
+use crate::algs::is_square;
+use crate::driver;
+use crate::traits::{Factorizer, Factors, Splitter};
+use num::bigint::BigInt;
+use num::{Integer, One, ToPrimitive};
+
+pub struct Hart {
+    n: BigInt,
+}
+
+impl Hart {
+    pub fn new(n: BigInt) -> Self {
+        Self { n }
+    }
+}
+
+impl Factorizer for Hart {
+    fn factorize(&self) -> Option<Factors> {
+        driver::factorize(&self.n, self)
+    }
+}
+
+impl Splitter for Hart {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        split(n, u64::MAX)
+    }
+}
+
+// Hart's one line factoring: s = ceil(sqrt(i n)) and s^2 mod n = t^2 give
+// gcd(s - t, n). Fast when p / q is near a fraction a / b with small a and b:
+// for i = a b, i n = (b p)(a q) is a product of two close integers, so
+// s^2 - i n is a small square, as in Fermat's method on the multiple i n.
+pub fn split(n: &BigInt, iterations: u64) -> Option<BigInt> {
+    if n.is_even() {
+        return Some(BigInt::from(2));
+    }
+    let mut done = 0;
+    if let Some(m) = n.to_u128() {
+        match split_native(m, iterations) {
+            Ok(d) => return d.map(BigInt::from),
+            Err(i) => done = i,
+        }
+    }
+    let mut i_n = n * done;
+    for _ in done..iterations {
+        i_n += n;
+        let mut s = i_n.sqrt();
+        if &s * &s < i_n {
+            s += 1u32;
+        }
+        let m = &s * &s % n;
+        if is_square(&m) {
+            let d = (s - m.sqrt()).gcd(n);
+            if !d.is_one() && d != *n {
+                return Some(d);
+            }
+        }
+    }
+    None
+}
+
+// The same search on machine words for odd n below 2^128. With s^2 - i n
+// taken from the remainder of the square root of i n, nothing but i n itself
+// can overflow; the number of iterations done by then is returned as the
+// error, for the search to go on with BigInt.
+fn split_native(n: u128, iterations: u64) -> Result<Option<u128>, u64> {
+    for i in 1..=iterations {
+        let Some(i_n) = n.checked_mul(i as u128) else {
+            return Err(i - 1);
+        };
+        let r = i_n.isqrt();
+        let rem = i_n - r * r;
+        let (s, m) = if rem == 0 { (r, 0) } else { (r + 1, (2 * r + 1 - rem) % n) };
+        let t = m.isqrt();
+        if t * t == m {
+            let d = (s - t).gcd(&n);
+            if d != 1 && d != n {
+                return Ok(Some(d));
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_hart() {
+        let n = bi!("6000000000035000000000039", 10);
+        let f = Hart::new(n).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "2000000000003 * 3000000000013");
+
+        let n = bi!("7000000050000000007", 10);
+        let f = Hart::new(n).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "1000000007 * 7000000001");
+
+        // 35n overflows u128, so the search finishes with BigInt.
+        let n = bi!("35000000000000000022000000000000000003", 10);
+        assert_eq!(split(&n, 1000), Some(bi!("5000000000000000001", 10)));
+        let n = bi!("6000000000000000000035000000000000000000039", 10);
+        assert_eq!(split(&n, 1000), Some(bi!("2000000000000000000003", 10)));
+    }
+}
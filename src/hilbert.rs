@@ -0,0 +1,996 @@
+// Hilbert class polynomials H_D(x) for the discriminants -1600 < D < 0 of
+// class number at most 4, as the coefficients of x^0, x^1, ... below the
+// leading 1. Their roots modulo p are the j-invariants of the curves over F_p
+// with complex multiplication by the order of discriminant D.
+pub const HILBERT: [(i64, &[&str]); 151] = [
+    (-3, &["0"]),
+    (-4, &["-1728"]),
+    (-7, &["3375"]),
+    (-8, &["-8000"]),
+    (-11, &["32768"]),
+    (-12, &["-54000"]),
+    (-15, &["-121287375", "191025"]),
+    (-16, &["-287496"]),
+    (-19, &["884736"]),
+    (-20, &["-681472000", "-1264000"]),
+    (-23, &["12771880859375", "-5151296875", "3491750"]),
+    (-24, &["14670139392", "-4834944"]),
+    (-27, &["12288000"]),
+    (-28, &["-16581375"]),
+    (-31, &["1566028350940383", "-58682638134", "39491307"]),
+    (-32, &["12167000000", "-52250000"]),
+    (-35, &["-134217728000", "117964800"]),
+    (-36, &["-1790957481984", "-153542016"]),
+    (
+        -39,
+        &[
+            "20919104368024767633",
+            "109873509788637459",
+            "-429878960946",
+            "331531596",
+        ],
+    ),
+    (-40, &["9103145472000", "-425692800"]),
+    (-43, &["884736000"]),
+    (-44, &["-653249011576832", "270413882112", "-1122662608"]),
+    (-48, &["6549518250000", "-2835810000"]),
+    (-51, &["6262062317568", "5541101568"]),
+    (-52, &["-567663552000000", "-6896880000"]),
+    (
+        -55,
+        &[
+            "-18577989025032784359375",
+            "172576736359017890625",
+            "-20948398473375",
+            "13136684625",
+        ],
+    ),
+    (
+        -56,
+        &[
+            "10064086044321563803648",
+            "2257767342088912896",
+            "2059647197077504",
+            "-16220384512",
+        ],
+    ),
+    (
+        -59,
+        &["374643194001883136", "-140811576541184", "30197678080"],
+    ),
+    (-60, &["153173312762625", "-37018076625"]),
+    (
+        -63,
+        &[
+            "-6256903954262253662109375",
+            "4558451243295023437500",
+            "-193068841781250",
+            "67515199875",
+        ],
+    ),
+    (-64, &["-7367066619912", "-82226316240"]),
+    (-67, &["147197952000"]),
+    (
+        -68,
+        &[
+            "-2089297506304000000000000",
+            "-318507038720000000000",
+            "-75843692160000000",
+            "-178211040000",
+        ],
+    ),
+    (-72, &["232381513792000000", "-377674768000"]),
+    (-75, &["5209253090426880", "654403829760"]),
+    (
+        -76,
+        &["-827237892283232256", "1128678666363648", "-784074438864"],
+    ),
+    (
+        -80,
+        &[
+            "422286883970526784000000",
+            "-171263969177632000000",
+            "-13028555239824000",
+            "-1597177172000",
+        ],
+    ),
+    (
+        -83,
+        &[
+            "549755813888000000000",
+            "-41490055168000000",
+            "2691907584000",
+        ],
+    ),
+    (
+        -84,
+        &[
+            "-5133201653210986057826304",
+            "88821246589810089394176",
+            "-5663679223085309952",
+            "-3196800946944",
+        ],
+    ),
+    (-88, &["15798135578688000000", "-6294842640000"]),
+    (-91, &["-3845689020776448", "10359073013760"]),
+    (
+        -92,
+        &[
+            "-6267542200571287109375",
+            "-263033266852296875",
+            "-12207823849750",
+        ],
+    ),
+    (
+        -96,
+        &[
+            "-984163224549635621646336",
+            "447805364111967209472",
+            "670421055192156288",
+            "-23340144296736",
+        ],
+    ),
+    (-99, &["-56171326053810176", "37616060956672"]),
+    (-100, &["-292143758886942437376", "-44031499226496"]),
+    (
+        -107,
+        &[
+            "337618789203968000000000",
+            "-6764523159552000000",
+            "129783279616000",
+        ],
+    ),
+    (
+        -108,
+        &[
+            "-1879994705688000000000",
+            "224179462188000000",
+            "-151013228706000",
+        ],
+    ),
+    (-112, &["1337635747140890625", "-274917323970000"]),
+    (-115, &["130231327260672000", "427864611225600"]),
+    (
+        -120,
+        &[
+            "4934510722321469030006784000000",
+            "-2588458316335175909376000000",
+            "26329406807264910336000",
+            "-883067971104000",
+        ],
+    ),
+    (-123, &["148809594175488000000", "1354146840576000"]),
+    (
+        -124,
+        &[
+            "-599530686551745232383",
+            "-874125972104525910",
+            "-1559739536377947",
+        ],
+    ),
+    (
+        -128,
+        &[
+            "-345363656226658026765625000000",
+            "-55499520947716391500000000",
+            "-395258439243352250000",
+            "-2729960418308000",
+        ],
+    ),
+    (
+        -132,
+        &[
+            "1656636925108948992000000000000",
+            "54984539729717250048000000000",
+            "-325211610485778048000000",
+            "-4736863498464000",
+        ],
+    ),
+    (
+        -136,
+        &[
+            "2422829169428572504087521656832",
+            "-1834607111282472051029311488",
+            "735960027609078992953344",
+            "-8151279336430848",
+        ],
+    ),
+    (
+        -139,
+        &[
+            "67408489017571610198016",
+            "-53041786755137667072",
+            "12183160834031616",
+        ],
+    ),
+    (
+        -144,
+        &[
+            "571751321233328637579104256",
+            "490453856866850787293184",
+            "269499185406087942528",
+            "-23578503968570400",
+        ],
+    ),
+    (-147, &["11356800389480448000000", "34848505552896000"]),
+    (-148, &["-7898242515936467904000000", "-39660183801072000"]),
+    (
+        -155,
+        &[
+            "37425860028464856284790784000000",
+            "20396251654725321097216000000",
+            "-44477871096357453824000",
+            "96905542950912000",
+        ],
+    ),
+    (
+        -156,
+        &[
+            "421266000645144840703921125633",
+            "-164993592496972989327022035",
+            "53074935443801207676942",
+            "-109914552886955148",
+        ],
+    ),
+    (
+        -160,
+        &[
+            "-293835053960432980416000000",
+            "13208221536779701382400000",
+            "-2940735389875294896000",
+            "-181195519824640800",
+        ],
+    ),
+    (-163, &["262537412640768000"]),
+    (
+        -168,
+        &[
+            "496644064976895846912000000000000000",
+            "-264691184105480095991808000000000",
+            "336511679671210230144000000",
+            "-483435712076832000",
+        ],
+    ),
+    (
+        -171,
+        &[
+            "-1311901521779155773721411584",
+            "8391550371275812148084736",
+            "472103267541360574464",
+            "694282057876537344",
+        ],
+    ),
+    (
+        -172,
+        &[
+            "-692660810326239000000000000",
+            "1164707517403692000000",
+            "-782759106183330000",
+        ],
+    ),
+    (
+        -180,
+        &[
+            "-6776421923145961044033929216000000",
+            "16660473763558887652278272000000",
+            "-2867757758882006477169664000",
+            "-2018504138609120000",
+        ],
+    ),
+    (
+        -184,
+        &[
+            "114574710497270997578522590458150912",
+            "38705419208160503264676104110080",
+            "5767007465145198439020847104",
+            "-3215890895076912384",
+        ],
+    ),
+    (-187, &["-3845689020776448000000", "4545336381788160000"]),
+    (
+        -192,
+        &[
+            "-1080060886113159937649308593750000",
+            "826335556188178615474500000000",
+            "15705521635909735050750000",
+            "-8041801037378436000",
+        ],
+    ),
+    (
+        -195,
+        &[
+            "-233490285492432753672585216000000",
+            "104773100319600336175104000000",
+            "25349140792043819237376000",
+            "11284411506057216000",
+        ],
+    ),
+    (
+        -196,
+        &[
+            "-2108010653658430719613224868701536256",
+            "250850701957837760512539510177792",
+            "-44864481851299856707307347968",
+            "-12626092121367165696",
+        ],
+    ),
+    (
+        -203,
+        &[
+            "31913605837856413057024000000000000",
+            "250634002097696556449792000000000",
+            "-83053272156952592384000000",
+            "27502410406723584000",
+        ],
+    ),
+    (
+        -208,
+        &[
+            "1463592841477827633000000000000",
+            "-3908668494888708948000000000",
+            "4032372412181255526000000",
+            "-47568078792050004000",
+        ],
+    ),
+    (
+        -211,
+        &[
+            "5310823021408898698117644288",
+            "277390576406111100862464",
+            "65873587288630099968",
+        ],
+    ),
+    (
+        -219,
+        &[
+            "110979720274963942538198675506593792",
+            "-15979705448736682450562851012608",
+            "831039118453558669939310592",
+            "155212323706544357376",
+        ],
+    ),
+    (
+        -220,
+        &[
+            "4189527305843979870968496890625",
+            "-6015443509589489085440390625",
+            "2531540097646020954716625",
+            "-172572544407169076625",
+        ],
+    ),
+    (
+        -228,
+        &[
+            "120020259495560805847424176128000000000000",
+            "58827548670433207062445836288000000000",
+            "-7985216535621460489954944000000",
+            "-399605224650084576000",
+        ],
+    ),
+    (
+        -232,
+        &["14871070713157137145512000000000", "-604729957849891344000"],
+    ),
+    (
+        -235,
+        &["11946621170462723407872000", "823177419449425920000"],
+    ),
+    (
+        -240,
+        &[
+            "51848746810441819437662737568196890625",
+            "-3213137488352330508627918491550000",
+            "15510636623637225985530243375",
+            "-1370337635584848362400",
+        ],
+    ),
+    (
+        -243,
+        &[
+            "3338586724673519616000000000",
+            "-3750657365033091072000000",
+            "1855762905734664192000",
+        ],
+    ),
+    (
+        -252,
+        &[
+            "398963060554172791168212890625",
+            "-2443574658947106282398437500",
+            "10887176246183122533468750",
+            "-4558302700896532039875",
+        ],
+    ),
+    (
+        -256,
+        &[
+            "-1064410681181869521037208505239142408",
+            "26925623396663008311375890966784",
+            "-1826592673506207200904172752",
+            "-6761166974781862161312",
+        ],
+    ),
+    (
+        -259,
+        &[
+            "4384296738486457527093398159228928",
+            "5493320206929896679139197321216",
+            "-368189472100537894019530752",
+            "9068999694311625523200",
+        ],
+    ),
+    (
+        -267,
+        &["531429662672621376897024000000", "19683091854079488000000"],
+    ),
+    (
+        -268,
+        &[
+            "-3189376432736929569384216000000000",
+            "32240842762858236972000000",
+            "-21667237292024856738000",
+        ],
+    ),
+    (
+        -275,
+        &[
+            "-3984711300201636241319486354007863066624",
+            "51213041627075282291106746090041376768",
+            "-1470671864720383632491493195776",
+            "42230108051959368384512",
+        ],
+    ),
+    (
+        -280,
+        &[
+            "1775168961518724506399346503073398784000000",
+            "-708555761206745670461365038563328000000",
+            "17602516524144666384420962098176000",
+            "-67667966893419063840000",
+        ],
+    ),
+    (
+        -283,
+        &[
+            "201371843156955365376000000000",
+            "90839236535446929408000000",
+            "89611323386832801792000",
+        ],
+    ),
+    (
+        -288,
+        &[
+            "40994594700208456153393000000000000",
+            "136478143044657426076564000000000",
+            "-87330008255955399131086000000",
+            "-142637765058468510772000",
+        ],
+    ),
+    (
+        -291,
+        &[
+            "21782000952710117887925312635418808680448",
+            "285389231946718842181542553187254272",
+            "10786588141336392324590050738176",
+            "188155567079341753466880",
+        ],
+    ),
+    (
+        -292,
+        &[
+            "-380259461042512404779990642688000000000000",
+            "45521551386379385369629968384000000000",
+            "-93693622511929038759497066112000000",
+            "-206287709860428304608000",
+        ],
+    ),
+    (
+        -307,
+        &[
+            "8987619631060626702336000000000",
+            "-5083646425734146162688000000",
+            "805016812009981390848000",
+        ],
+    ),
+    (
+        -312,
+        &[
+            "1698899690981885675579246225669492736000000000000",
+            "-152340504750882110373595179663329280000000000",
+            "1411168483733488619338991640960000000",
+            "-1258031100283439093280000",
+        ],
+    ),
+    (
+        -315,
+        &[
+            "16319518649540946810407747584000000",
+            "256755031834901461034493542400000",
+            "116880346596078822090604544000",
+            "1641640623633325773619200",
+        ],
+    ),
+    (
+        -323,
+        &[
+            "-121974636783103604190112617857024000000000000",
+            "73804562114102168041788801024000000000000",
+            "-494846073292941121091010560000000",
+            "3317765887009185280000000",
+        ],
+    ),
+    (
+        -328,
+        &[
+            "88955608603044673650138130944000000000000000",
+            "54802167111836784369290132453376000000000",
+            "11610744584144462730131436503424000000",
+            "-5127512346913614444576000",
+        ],
+    ),
+    (
+        -331,
+        &[
+            "56176242840389398230218488594563072",
+            "368729929041040103875232661504",
+            "6647404730173793386463232",
+        ],
+    ),
+    (
+        -340,
+        &[
+            "43039377624755967291385639037347037184000000",
+            "5906485031594874833231597894020684185600000",
+            "-54548817402421378465247510316573696000",
+            "-14383245771217510630675200",
+        ],
+    ),
+    (
+        -352,
+        &[
+            "233814863662235465757810993000000000000",
+            "149685745558232337507189972000000000",
+            "-99328134588221245548396654000000",
+            "-39625012256717991533364000",
+        ],
+    ),
+    (
+        -355,
+        &[
+            "167490001660588917859010199158784000000",
+            "-24013762453779394698078584832000000",
+            "6828932041616339922516443136000",
+            "50912008581334742581248000",
+        ],
+    ),
+    (
+        -363,
+        &[
+            "1577314437358442913340940353536000000000000",
+            "-496864268553728774541064273920000000000",
+            "45688143672322270430861721600000000",
+            "98823634118413525094400000",
+        ],
+    ),
+    (
+        -372,
+        &[
+            "41393149892607462736698558825033501904896000000000000",
+            "1755509254864401819594526832548625909760000000000",
+            "-2969541010382978868435960918595200000000",
+            "-206603714804587147622880000",
+        ],
+    ),
+    (
+        -379,
+        &[
+            "15443600047689011948024601807415148544",
+            "-121567791009880876719538528321536",
+            "364395404104624239018246144",
+        ],
+    ),
+    (
+        -387,
+        &[
+            "587533119951491680960512000000000000",
+            "747948304897905210163200000000000",
+            "-1543710173513675490459648000000",
+            "692535742940813486100480000",
+        ],
+    ),
+    (
+        -388,
+        &[
+            "-1121692648948590091501551223636881408000000000000",
+            "208224136957169320201407896480139264000000000",
+            "-20542159225989612130996373047535232000000",
+            "-750062398364686994581728000",
+        ],
+    ),
+    (
+        -400,
+        &[
+            "87448873738295790450948276123544550117376",
+            "-19075061455767889406477974994607212544",
+            "12869286863161864184636279443710336",
+            "-1938773508354872717845384224",
+        ],
+    ),
+    (
+        -403,
+        &[
+            "-108844203402491055833088000000",
+            "2452811389229331391979520000",
+        ],
+    ),
+    (
+        -408,
+        &[
+            "13375974716483932888129605820405217248677888000000000000",
+            "-334918514756463762318006309600841904719872000000000",
+            "218066148024051247931306674050097536000000",
+            "-3622859125108878497350176000",
+        ],
+    ),
+    (
+        -427,
+        &[
+            "155041756222618916546936832000000",
+            "15611455512523783919812608000",
+        ],
+    ),
+    (
+        -435,
+        &[
+            "-12512019875237835915942574589201734434816000000",
+            "42866222697779107335351550466659555737600000",
+            "87465379468169320817492479772196864000",
+            "28597298728131202056826060800",
+        ],
+    ),
+    (
+        -448,
+        &[
+            "-8964424282273362890505339044524081787109375",
+            "18314847446238545696830716579562500000000",
+            "-1251995474985759392628697477841250000",
+            "-75579535015741588088518020000",
+        ],
+    ),
+    (
+        -475,
+        &[
+            "-241309172434875116180341986667714653978624",
+            "207773856463139688132677943482420035584",
+            "-18897945215696864441083229261266944",
+            "544368813426921255662610284544",
+        ],
+    ),
+    (
+        -483,
+        &[
+            "-296241507936739247491345278560108544000000000000",
+            "160587932046974848398336021151875072000000000",
+            "9557426544972522152310585774047232000000",
+            "966618711103413979025620992000",
+        ],
+    ),
+    (
+        -499,
+        &[
+            "4671133182399954782798673154437441310949376",
+            "-6063717825494266394722392560011051008",
+            "3005101108071026200706725969920",
+        ],
+    ),
+    (
+        -507,
+        &[
+            "52801843586884277419825606742991962112000000000000",
+            "2658551097838253705341260639403769856000000000",
+            "91537611307702496719179485710123008000000",
+            "5262585338995033221326389248000",
+        ],
+    ),
+    (
+        -520,
+        &[
+            "171517475891022372428505519185548559222346497654784000000",
+            "-78006534528871949845908360976579586206001479680000000",
+            "46650003139146307922421888174845453223975936000",
+            "-12958889442406058296422344736000",
+        ],
+    ),
+    (
+        -532,
+        &[
+            "-19077542993352945680961028994697271308288000000000000",
+            "5131537740610192962070880163006969643272192000000000",
+            "-160054212938390343773833947283393690785408000000",
+            "-29478909019098139074177479136000",
+        ],
+    ),
+    (
+        -547,
+        &[
+            "83303937570678403968635240448000000000",
+            "-139712328431787827943469744128000000",
+            "81297395539631654721637478400000",
+        ],
+    ),
+    (
+        -555,
+        &[
+            "-532755731205331063356397364951543957176713216000000",
+            "19282254568556435196991625190065063388512256000000",
+            "7191013406366483381037450688276469907456000",
+            "138859536630220704987259502592000",
+        ],
+    ),
+    (
+        -568,
+        &[
+            "17903747548118085544966894162888109264474112000000000000",
+            "-20244861194040338252021384794239225557256192000000000",
+            "5960215994584814927107650154330552605647232000000",
+            "-328731508303364809994652861984000",
+        ],
+    ),
+    (
+        -592,
+        &[
+            "62432994413265034555246757429466475569000000000000",
+            "-466359042858849293316032894138251092000000000",
+            "313250432161122948352163248725179238000000",
+            "-1572930194931239851927605196116000",
+        ],
+    ),
+    (
+        -595,
+        &[
+            "-91399742601830803813322386656934773129216000000",
+            "483054636550112292687021684688517332992000000",
+            "8752111455147508300981595950899265536000",
+            "1908606683491595666107623383040000",
+        ],
+    ),
+    (
+        -603,
+        &[
+            "469351599148575084436348037234688000000000000",
+            "3412681538174356730332626026496000000000",
+            "-7118624290306318120963251634176000000",
+            "3189372971004509360884026494976000",
+        ],
+    ),
+    (
+        -627,
+        &[
+            "-1261687189208313891495979730091871567872000000000000",
+            "526326624169690832922357632213666758656000000000",
+            "3563858169242172480409901737583233204224000000",
+            "14586137722924213400310156521472000",
+        ],
+    ),
+    (
+        -643,
+        &[
+            "308052554652302847380880841299197952000000000",
+            "-6300378505047247876499651797450752000000",
+            "39545575162726134099492467011584000",
+        ],
+    ),
+    (
+        -652,
+        &[
+            "-18095625621665522953693950872675200892692248000000000",
+            "102561728837719322645921325412908000000",
+            "-68925893036109279891085639286946000",
+        ],
+    ),
+    (
+        -667,
+        &[
+            "-278701754438991300992352387072000000000000000",
+            "-147087485221823269890900432519168000000000",
+            "-3737847346141410401145461932032000000",
+            "172524940705544715709707399634944000",
+        ],
+    ),
+    (
+        -708,
+        &[
+            "4046686423378034814414234559373865948538701215210194862739456000000000000",
+            "3603887011528002652771717224491220641587422892784070051840000000000",
+            "-2854565250565963840094617979015298078098347812480000000",
+            "-2012303924332635494819557244440800000",
+        ],
+    ),
+    (
+        -715,
+        &[
+            "13189879204176058896562640516998642620432384000000",
+            "94657547256854352451418607502680693669888000000",
+            "60156378344564221943954774472086041657344000",
+            "3038922093329613647424771157499904000",
+        ],
+    ),
+    (
+        -723,
+        &[
+            "43799003445375960815587788104700084092928000000000000000",
+            "-17437817166277457429521660531780027831812096000000000",
+            "8222450770908698023546828197247145547399168000000",
+            "4855690107103225136120718536060928000",
+        ],
+    ),
+    (
+        -760,
+        &[
+            "57390991709103678336339431944416743303984993656228540622045184000000",
+            "-8762694788548498478760416933120597566268079681131589510758400000",
+            "262960509575258849119050573504013616920976671774792704000",
+            "-41045008988631123111685822548134227200",
+        ],
+    ),
+    (
+        -763,
+        &[
+            "1212202634617724845661254714392576000000000000000",
+            "3730143008151395358758986101112700928000000000",
+            "11764579526453656222964578511153528832000000",
+            "48688224497542950284157258615128064000",
+        ],
+    ),
+    (
+        -772,
+        &[
+            "-4039979678479578220330132982722340932044073244946432000000000000",
+            "-1654219429424921222911088262751088404746562249930752000000000",
+            "-730409189972766569984362477406681962614314316392064000000",
+            "-81104350841312411963776730201270496000",
+        ],
+    ),
+    (
+        -795,
+        &[
+            "1580866394929445594613317271657673734190830966521462784000000",
+            "96989374802114211792220362019627433906928110027145216000000",
+            "1962512368737475150054890329369747830206508302336000",
+            "294853904675299611949375562546552832000",
+        ],
+    ),
+    (
+        -883,
+        &[
+            "167990285381627318187575520800123387904000000000",
+            "-151960111125245282033875619529124478976000000",
+            "34903934341011819039224295011933392896000",
+        ],
+    ),
+    (
+        -907,
+        &[
+            "149161274746524841328545894969274007552000000000",
+            "39181594208014819617565811575376314368000000",
+            "123072080721198402394477590506838687744000",
+        ],
+    ),
+    (
+        -928,
+        &[
+            "221147287295840631009155378215919896241234993293617000000000000",
+            "13390564998216290984073146179239751905642859284000000000",
+            "-8992980876865725569995845414882451608653638542000000",
+            "-365698321891389219219142531076614125108000",
+        ],
+    ),
+    (
+        -955,
+        &[
+            "438953058221654415262613188100773336407392447044238966784000000",
+            "520207875218635547684744626511303352924946915393536000000",
+            "396469707692607651662987973604670339150203846656000",
+            "1456880094856940116294718071366713311232000",
+        ],
+    ),
+    (
+        -1003,
+        &[
+            "15040125689821293744115482557611348328448000000000000",
+            "1305202673705533598197444367081354312024064000000000",
+            "-204493994631228266186213761658603748458496000000",
+            "16219528503217062422459730048347378577408000",
+        ],
+    ),
+    (
+        -1012,
+        &[
+            "-204344290478354698106731378125784194576718103432833630334976000000000000",
+            "1243508019466325039942928040075544459231301705571821435488256000000000",
+            "-127409933077387882483393397275452601210672521773684542789248000000",
+            "-25320300665394312513202440887044222231008000",
+        ],
+    ),
+    (
+        -1027,
+        &[
+            "271046093357449955035386983426329999080765259776000000000000",
+            "164592522336657395778809121398601659935044403200000000000",
+            "-41547404176734721779832688271619104304005120000000",
+            "52960452958968182770743647384658280611840000",
+        ],
+    ),
+    (
+        -1227,
+        &[
+            "2513550635275580846572126510888944466103176257943640211456000000000000000",
+            "89252949391959745426288430543461455160950172376987709997056000000000",
+            "5282646588767618158994396140387285593346806931114470408192000000",
+            "619638890847298092963653036606353098743021568000",
+        ],
+    ),
+    (
+        -1243,
+        &[
+            "30540293156908205255120060127032312602199851008000000000000",
+            "-54665859623503521460552388837431864605259333632000000000",
+            "26521598516319200744664388741019144869638045696000000",
+            "1266871605300222128375795939246750405038301184000",
+        ],
+    ),
+    (
+        -1387,
+        &[
+            "2052723014407052457647477199445183281503328534528000000000000",
+            "36686449491372953371348633076156538920834099576832000000000",
+            "8421392423043512311845823062070841518683467022336000000",
+            "649705640341533249055461232040056199884943609856000",
+        ],
+    ),
+    (
+        -1411,
+        &[
+            "259898672030231371072634921991495299708204070671565541211890306346215538688",
+            "563159340355333157360236566159729674583227014555157757651685811744145408",
+            "19568314960219288785284224576189610670941760495017044362657792",
+            "1780126746705689756102562231651060896708610079948800",
+        ],
+    ),
+    (
+        -1435,
+        &[
+            "-4009811510734177961140258455491639109235283276327883374802990268416000000",
+            "2539970793779946148723473087883821948343643667859030533390886502400000",
+            "105161502065491843193116493512870644772977750388041962225664000",
+            "4835907878329132222450395857259654466718969836339200",
+        ],
+    ),
+    (
+        -1467,
+        &[
+            "4750778730158046729996627074508314393022035482908819456000000000000000",
+            "19361523208256264971288945998620952474692182081536000000000",
+            "-40389436387354150167355479516145857227679829327872000000",
+            "18095625621654510801615355531263454706630064771072000",
+        ],
+    ),
+    (
+        -1507,
+        &[
+            "946755971011460406830147750660957594274801022094278656000000000000",
+            "-131730022847167071512725748945146741381253488296591360000000000",
+            "3693591679022156272138192761442250249057420055674880000000",
+            "92304656744815388412175046838197961483773831208960000",
+        ],
+    ),
+    (
+        -1555,
+        &[
+            "179277385817055839939036171839607344168985308854293076933328502784000000",
+            "-153802169705179237851782806689208034512876098346328765182771200000000",
+            "36521008026523717023567141651588968008073653196177682701746176000",
+            "634043412248649501919536531936002831564519413161984000",
+        ],
+    ),
+];
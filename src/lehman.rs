@@ -0,0 +1,147 @@
+use crate::algs::is_square;
+use crate::driver;
+use crate::sieve::{self, Sieve};
+use crate::traits::{Factorizer, Factors, Splitter};
+use num::bigint::BigInt;
+use num::integer::Roots;
+use num::{Integer, ToPrimitive, Zero};
+
+// Trial division bound before the square search.
+const TRIAL_BOUND: u64 = 100_000;
+
+// Largest input searched on machine words: 4kn and a^2 stay below 2^123 for
+// every k up to n^(1/3) + 1.
+const NATIVE_BITS: u64 = 90;
+
+pub struct Lehman {
+    n: BigInt,
+}
+
+impl Lehman {
+    pub fn new(n: BigInt) -> Self {
+        Self { n }
+    }
+}
+
+impl Factorizer for Lehman {
+    fn factorize(&self) -> Option<Factors> {
+        driver::factorize(&self.n, self)
+    }
+}
+
+impl Splitter for Lehman {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        split(n)
+    }
+}
+
+// Lehman's method: with no factor up to r = n^(1/3), some k <= r has
+// a^2 - 4kn = b^2 for an a within n^(1/6) / (4 sqrt(k)) of sqrt(4kn). Finds a
+// factor of every composite n in O(n^(1/3)) steps, and returns None only for
+// primes. The square search runs before the trial division beyond
+// TRIAL_BOUND, as it is what finds factors of similar size quickly.
+pub fn split(n: &BigInt) -> Option<BigInt> {
+    if n.bits() <= NATIVE_BITS {
+        return split_native(n.to_u128()?).map(BigInt::from);
+    }
+    let r = n.cbrt() + 1u32;
+    let r_u64 = r.to_u64()?;
+    let divides = |d: &u64| *d <= r_u64 && BigInt::from(*d) < *n;
+    if let Some(&d) = sieve::primes(TRIAL_BOUND)
+        .iter()
+        .take_while(|d| divides(d))
+        .find(|&&d| (n % d).is_zero())
+    {
+        return Some(BigInt::from(d));
+    }
+
+    let sixth = n.to_f64()?.powf(1.0 / 6.0);
+    let mut k = BigInt::from(1u32);
+    while k <= r {
+        let kn4 = &k * n * 4u32;
+        let mut a = kn4.sqrt();
+        if &a * &a < kn4 {
+            a += 1u32;
+        }
+        let width = (sixth / (4.0 * k.to_f64()?.sqrt())) as u64;
+        let a_max = kn4.sqrt() + width + 1u32;
+        while a <= a_max {
+            let b2 = &a * &a - &kn4;
+            if is_square(&b2) {
+                let d = (&a + b2.sqrt()).gcd(n);
+                if d > BigInt::from(1u32) && d < *n {
+                    return Some(d);
+                }
+            }
+            a += 1u32;
+        }
+        k += 1u32;
+    }
+
+    Sieve::new(TRIAL_BOUND + 1, u64::MAX)
+        .take_while(divides)
+        .find(|&d| (n % d).is_zero())
+        .map(BigInt::from)
+}
+
+// The same search on machine words, for n below 2^NATIVE_BITS.
+fn split_native(n: u128) -> Option<u128> {
+    let r = n.cbrt() + 1;
+    let divides = |d: &u64| *d as u128 <= r && (*d as u128) < n;
+    if let Some(&d) = sieve::primes(TRIAL_BOUND)
+        .iter()
+        .take_while(|d| divides(d))
+        .find(|&&d| u128::is_multiple_of(n, d as u128))
+    {
+        return Some(d as u128);
+    }
+
+    let sixth = (n as f64).powf(1.0 / 6.0);
+    for k in 1..=r {
+        let kn4 = 4 * k * n;
+        let root = kn4.isqrt();
+        let mut a = if root * root < kn4 { root + 1 } else { root };
+        let width = (sixth / (4.0 * (k as f64).sqrt())) as u128;
+        while a <= root + width + 1 {
+            let b2 = a * a - kn4;
+            let b = b2.isqrt();
+            if b * b == b2 {
+                let d = (a + b).gcd(&n);
+                if d > 1 && d < n {
+                    return Some(d);
+                }
+            }
+            a += 1;
+        }
+    }
+
+    Sieve::new(TRIAL_BOUND + 1, u64::MAX)
+        .take_while(divides)
+        .find(|&d| u128::is_multiple_of(n, d as u128))
+        .map(u128::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_lehman() {
+        // p / q is close to 2 / 3.
+        let n = bi!("6000000000035000000000039", 10);
+        let f = Lehman::new(n).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "2000000000003 * 3000000000013");
+
+        let n = bi!("7000000050000000007", 10);
+        let f = Lehman::new(n).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "1000000007 * 7000000001");
+
+        assert_eq!(split(&BigInt::from(1000003u32)), None);
+        assert_eq!(split(&BigInt::from(1000003u64 * 17)), Some(BigInt::from(17u32)));
+
+        // Beyond NATIVE_BITS.
+        let n = bi!("60000000000025900000000002403", 10);
+        assert_eq!(split(&n), Some(bi!("300000000000089", 10)));
+    }
+}
@@ -0,0 +1,166 @@
+use num::bigint::BigInt;
+use num::{Integer, One};
+use std::collections::HashMap;
+
+// Dependencies tried per round of linear algebra.
+const DEPENDENCIES: usize = 64;
+
+#[derive(Clone)]
+pub struct Relation {
+    // y^2 = (-1)^e0 * prod p_j^ej * large^2 mod n, with the columns of the
+    // odd and even exponents listed in `factors` with multiplicity. Column 0
+    // is the sign, column j + 1 the j-th factor base prime.
+    pub y: BigInt,
+    pub factors: Vec<usize>,
+    pub large: u64,
+}
+
+impl Relation {
+    // Two partial relations sharing their large prime make a full one.
+    fn combine(&self, other: &Self, n: &BigInt) -> Self {
+        Self {
+            y: &self.y * &other.y % n,
+            factors: self.factors.iter().chain(&other.factors).copied().collect(),
+            large: self.large,
+        }
+    }
+}
+
+// Full relations, and partial ones (large > 1) waiting for a partner with the
+// same large prime.
+#[derive(Default)]
+pub struct Relations {
+    full: Vec<Relation>,
+    partials: HashMap<u64, Relation>,
+}
+
+impl Relations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn count(&self) -> usize {
+        self.full.len()
+    }
+    pub fn add(&mut self, r: Relation, n: &BigInt) {
+        if r.large == 1 {
+            self.full.push(r);
+        } else if let Some(q) = self.partials.get(&r.large) {
+            self.full.push(q.combine(&r, n));
+        } else {
+            self.partials.insert(r.large, r);
+        }
+    }
+
+    // Tries the congruences of squares given by the dependencies among the
+    // full relations over the factor base `primes`.
+    pub fn factor(&self, primes: &[u64], n: &BigInt) -> Option<BigInt> {
+        dependencies(&self.full, primes.len() + 1)
+            .iter()
+            .find_map(|dependency| square_root(&self.full, dependency, primes, n))
+    }
+}
+
+// Sets of relations whose factors multiply to a square: the null space of the
+// exponent parity matrix, by Gaussian elimination with one bit per relation.
+fn dependencies(relations: &[Relation], columns: usize) -> Vec<Vec<usize>> {
+    let r = relations.len();
+    let words = r.div_ceil(64);
+    let mut rows = vec![vec![0u64; words]; columns];
+    for (i, rel) in relations.iter().enumerate() {
+        for &c in &rel.factors {
+            rows[c][i / 64] ^= 1 << (i % 64);
+        }
+    }
+
+    let mut pivots: Vec<usize> = Vec::new();
+    for col in 0..r {
+        let bit = 1u64 << (col % 64);
+        let rank = pivots.len();
+        let Some(pivot) = (rank..columns).find(|&i| rows[i][col / 64] & bit != 0) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        let (head, tail) = rows.split_at_mut(rank);
+        let (pivot_row, tail) = tail.split_first_mut().unwrap();
+        for row in head.iter_mut().chain(tail.iter_mut()) {
+            if row[col / 64] & bit != 0 {
+                row.iter_mut().zip(pivot_row.iter()).for_each(|(w, p)| *w ^= p);
+            }
+        }
+        pivots.push(col);
+    }
+
+    (0..r)
+        .filter(|col| pivots.binary_search(col).is_err())
+        .take(DEPENDENCIES)
+        .map(|free| {
+            let mut dependency = vec![free];
+            for (row, &col) in rows.iter().zip(&pivots) {
+                if row[free / 64] & (1 << (free % 64)) != 0 {
+                    dependency.push(col);
+                }
+            }
+            dependency
+        })
+        .collect()
+}
+
+// gcd(X - Y, n) for X^2 = Y^2 mod n built from a dependency.
+fn square_root(
+    relations: &[Relation],
+    dependency: &[usize],
+    primes: &[u64],
+    n: &BigInt,
+) -> Option<BigInt> {
+    let mut x = BigInt::one();
+    let mut y = BigInt::one();
+    let mut exponents = vec![0u64; primes.len() + 1];
+    for &i in dependency {
+        let rel = &relations[i];
+        x = x * &rel.y % n;
+        y = y * rel.large % n;
+        for &c in &rel.factors {
+            exponents[c] += 1;
+        }
+    }
+    for (j, &e) in exponents.iter().enumerate().skip(1) {
+        if e > 0 {
+            y = y * BigInt::from(primes[j - 1]).modpow(&BigInt::from(e / 2), n) % n;
+        }
+    }
+    let d = (x - y).gcd(n);
+    if d.is_one() || d == *n {
+        None
+    } else {
+        Some(d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relations() {
+        // n = 7 * 13; 10^2 = 9 = 3^2, 12^2 = 53 = -38 = -2 * 19: only the
+        // first is usable, and gives gcd(10 - 3, 91) = 7.
+        let n = BigInt::from(91);
+        let primes = [2, 3, 19];
+        let mut relations = Relations::new();
+        let rel = |y: u32, factors: Vec<usize>, large| Relation {
+            y: BigInt::from(y),
+            factors,
+            large,
+        };
+        relations.add(rel(12, vec![0, 1, 3], 1), &n);
+        relations.add(rel(10, vec![2, 2], 1), &n);
+        assert_eq!(relations.count(), 2);
+        assert_eq!(relations.factor(&primes, &n), Some(BigInt::from(7)));
+
+        // A partial relation is only counted once its large prime repeats.
+        relations.add(rel(5, vec![], 5), &n);
+        assert_eq!(relations.count(), 2);
+        relations.add(rel(6, vec![], 5), &n);
+        assert_eq!(relations.count(), 3);
+    }
+}
@@ -1,36 +1,343 @@
-use crate::cli::{Algorithm, OutputFormat};
-use crate::traits::Factorizer;
-use num::BigInt;
+use crate::certify::Certificate;
+use crate::cli::{Algorithm, Cli, Command, OutputFormat};
+use crate::traits::{Factorizer, Factors, Status};
+use clap::ValueEnum;
+use num::{BigInt, One};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use std::{fs, io, process};
 
 mod algs;
+mod auto;
+mod certify;
+mod cfrac;
 mod cli;
-mod consts;
+mod driver;
 mod ecm;
+mod ecpp;
 mod elliptic_curve;
+mod expr;
 mod fermat;
 mod galois_field;
+mod hart;
+mod hilbert;
+mod lehman;
+mod linalg;
 mod mac;
+mod native;
+mod output;
+mod parallel;
+mod pm1;
+mod pp1;
+mod qs;
+mod range;
+mod rho;
+mod sieve;
+mod squfof;
 mod traits;
 mod trial;
 
 fn main() {
     let cli = cli::parse();
-    let n_str: String = cli.n.trim().to_string();
-    let n: BigInt = BigInt::parse_bytes(n_str.as_bytes(), 10).unwrap();
+    match &cli.command {
+        Some(Command::VerifyCert { file }) => process::exit(verify_cert(file)),
+        Some(Command::Primes { a, b }) => {
+            list_primes(*a, *b);
+            return;
+        }
+        Some(Command::Pi { x }) => {
+            println!("{}", sieve::pi(*x));
+            return;
+        }
+        Some(Command::NextPrime { n }) => {
+            println!("{}", algs::next_prime(n));
+            return;
+        }
+        Some(Command::PrevPrime { n }) => match algs::prev_prime(n) {
+            Some(p) => {
+                println!("{p}");
+                return;
+            }
+            None => {
+                eprintln!("no prime below {n}");
+                process::exit(1);
+            }
+        },
+        Some(Command::FactorRange {
+            a,
+            b,
+            output_format,
+        }) => {
+            factor_range(*a, *b, *output_format);
+            return;
+        }
+        None => {}
+    }
+    let jobs = parallel::threads(cli.jobs);
+    if let Some(file) = cli.input.as_deref().or(cli.n.as_deref().filter(|&n| n == "-")) {
+        process::exit(batch(&cli, file, jobs));
+    }
+    let n = match expr::parse(cli.n.as_deref().unwrap_or_default()) {
+        Ok(n) => n,
+        Err(e) => {
+            eprintln!("invalid input: {e}");
+            process::exit(1);
+        }
+    };
+    let start = Instant::now();
+    let Some((mut factors, sigmas)) = factorize(&cli, n.clone(), jobs) else {
+        eprintln!("no factorization for {n}");
+        process::exit(1);
+    };
+    factors.complete();
+    let elapsed = start.elapsed();
+    let certificate = certify(&cli, &mut factors, jobs);
+    println!(
+        "{}",
+        render(&cli, "n", &n, &factors, &sigmas, elapsed, certificate.as_ref())
+    );
+    // A composite left unsplit makes the factorization incomplete.
+    if !factors.is_complete() {
+        eprintln!("unfactored part: {}", factors.unfactored());
+        process::exit(1);
+    }
+}
 
+// The factorization of n with the chosen algorithm, and for ECM the sigma of
+// each curve that found a factor.
+fn factorize(cli: &Cli, n: BigInt, jobs: usize) -> Option<(Factors, Vec<u64>)> {
     let factors = match cli.algorithm {
+        Algorithm::Auto => auto::Auto::new(n).jobs(jobs).factorize(),
         Algorithm::Trial => trial::Trial::new(n).factorize(),
         Algorithm::Fermat => fermat::Fermat::new(n).factorize(),
-        Algorithm::ECM => ecm::ECM::new(n).factorize(),
-        _ => None,
-    }.unwrap();
+        Algorithm::Rho => rho::Rho::new(n).jobs(jobs).factorize(),
+        Algorithm::Pm1 => {
+            let b1 = cli.b1.unwrap_or(pm1::DEFAULT_B1);
+            let b2 = cli.b2.unwrap_or(b1.saturating_mul(100));
+            pm1::Pm1::new(n).bounds(b1, b2).jobs(jobs).factorize()
+        }
+        Algorithm::Pp1 => {
+            let b1 = cli.b1.unwrap_or(pp1::DEFAULT_B1);
+            let b2 = cli.b2.unwrap_or(b1.saturating_mul(100));
+            let seeds = cli.seeds.unwrap_or(pp1::DEFAULT_SEEDS);
+            pp1::Pp1::new(n)
+                .bounds(b1, b2)
+                .seeds(seeds)
+                .jobs(jobs)
+                .factorize()
+        }
+        Algorithm::QS => qs::QS::new(n).factorize(),
+        Algorithm::Cfrac => cfrac::Cfrac::new(n).factorize(),
+        Algorithm::Squfof => squfof::Squfof::new(n).factorize(),
+        Algorithm::Lehman => lehman::Lehman::new(n).factorize(),
+        Algorithm::Hart => hart::Hart::new(n).factorize(),
+        Algorithm::Ecm => {
+            let b1 = cli.b1.unwrap_or(ecm::DEFAULT_B1);
+            let b2 = cli.b2.unwrap_or(b1.saturating_mul(100));
+            let ecm = ecm::Ecm::new(n)
+                .bounds(b1, b2)
+                .curve(cli.curve)
+                .torsion(cli.torsion)
+                .sigma(cli.sigma)
+                .curves(cli.curves.unwrap_or(ecm::DEFAULT_CURVES))
+                .jobs(jobs);
+            return ecm.factorize().map(|f| (f, ecm.sigmas()));
+        }
+    };
+    factors.map(|f| (f, Vec::new()))
+}
 
+// With `--certify`, a verified certificate for the prime factors, which marks
+// the probable primes it covers as proven. Primes it does not cover, and a
+// failed verification, are reported on stderr.
+fn certify(cli: &Cli, factors: &mut Factors, jobs: usize) -> Option<Certificate> {
+    cli.certify.then(|| {
+        let (certificate, failed) = certify::factors(factors, jobs);
+        for p in failed {
+            eprintln!("no certificate for {p}");
+        }
+        match certificate.verify() {
+            Ok(()) => {
+                for f in factors.get_factors_vector() {
+                    let (p, _) = f.pe();
+                    if factors.status(&p) == Some(Status::ProbablePrime) && certificate.proves(&p) {
+                        factors.set_status(&p, Status::Prime);
+                    }
+                }
+            }
+            Err(e) => eprintln!("invalid certificate: {e}"),
+        }
+        certificate
+    })
+}
+
+// The result for n in the chosen format: `label = factors` followed by the
+// ECM sigmas and the certificate steps, or a JSON document.
+fn render(
+    cli: &Cli,
+    label: &str,
+    n: &BigInt,
+    factors: &Factors,
+    sigmas: &[u64],
+    elapsed: Duration,
+    certificate: Option<&Certificate>,
+) -> String {
     let result = match cli.output_format {
         OutputFormat::List => factors.get_factors_list(),
         OutputFormat::FlatList => factors.get_factors_flat_list(),
         OutputFormat::Expr => factors.get_factors_expr(),
-        _ => format!("{factors}"),
+        OutputFormat::Json => {
+            let algorithm = cli.algorithm.to_possible_value().unwrap();
+            return output::json(n, algorithm.get_name(), elapsed, factors, sigmas, certificate);
+        }
+    };
+    let mut text = format!("{label} = {result}");
+    if !sigmas.is_empty() {
+        let sigmas: Vec<String> = sigmas.iter().map(u64::to_string).collect();
+        text += &format!("\nsigma = {}", sigmas.join(", "));
+    }
+    for step in certificate.map_or(&[][..], |c| c.steps()) {
+        text += &format!("\n{step}");
+    }
+    text
+}
+
+// `fr -` and `fr --input FILE`: factors the integer on each nonblank line,
+// several lines at once with `--jobs`, and streams the results in input
+// order, tagged with the line. Returns exit status 1 if some line failed or
+// was left incomplete, or if the input could not be read to the end.
+fn batch(cli: &Cli, file: &str, jobs: usize) -> i32 {
+    let reader: Box<dyn BufRead + Send> = if file == "-" {
+        Box::new(BufReader::new(io::stdin()))
+    } else {
+        match fs::File::open(file) {
+            Ok(f) => Box::new(BufReader::new(f)),
+            Err(e) => {
+                eprintln!("{file}: {e}");
+                return 1;
+            }
+        }
     };
+    // A read error, including a line that is not UTF-8, ends the input.
+    let read_error = Mutex::new(None);
+    let lines = reader
+        .lines()
+        .enumerate()
+        .map_while(|(i, line)| match line {
+            Ok(line) => Some((i, line)),
+            Err(e) => {
+                *read_error.lock().unwrap() = Some(format!("line {}: {e}", i + 1));
+                None
+            }
+        })
+        .filter(|(_, line)| !line.trim().is_empty());
 
-    println!("n = {result}");
+    // Each line gets one thread when the lines themselves run in parallel.
+    let inner_jobs = if jobs > 1 { 1 } else { jobs };
+    let run = |(i, line): (usize, String)| {
+        let input = line.trim().to_string();
+        let result = expr::parse(&input).and_then(|n| {
+            let start = Instant::now();
+            let (mut factors, sigmas) = factorize(cli, n.clone(), inner_jobs)
+                .ok_or_else(|| format!("no factorization for {n}"))?;
+            factors.complete();
+            let elapsed = start.elapsed();
+            let certificate = certify(cli, &mut factors, inner_jobs);
+            let certificate = certificate.as_ref();
+            let text = render(cli, &input, &n, &factors, &sigmas, elapsed, certificate);
+            Ok((text, factors.unfactored()))
+        });
+        (i + 1, input, result)
+    };
+
+    let mut status = 0;
+    let mut out = io::stdout().lock();
+    parallel::ordered(jobs, lines, run, |(line, input, result)| match result {
+        Ok((text, unfactored)) => {
+            let text = match cli.output_format {
+                OutputFormat::Json => output::tagged(line, &input, &text),
+                _ => text,
+            };
+            let written = writeln!(out, "{text}").and_then(|()| out.flush()).is_ok();
+            if !unfactored.is_one() {
+                eprintln!("line {line}: unfactored part {unfactored}: {input}");
+                status = 1;
+            }
+            written
+        }
+        Err(e) => {
+            eprintln!("line {line}: {e}: {input}");
+            status = 1;
+            true
+        }
+    });
+    if let Some(e) = read_error.into_inner().unwrap() {
+        eprintln!("{e}");
+        status = 1;
+    }
+    status
+}
+
+// `fr primes`: streams the primes in [a, b], one per line, until stdout closes.
+fn list_primes(a: u64, b: u64) {
+    let mut out = BufWriter::new(io::stdout().lock());
+    for p in sieve::Sieve::new(a, b) {
+        if writeln!(out, "{p}").is_err() {
+            return;
+        }
+    }
+    let _ = out.flush();
+}
+
+// `fr factor-range`: one line per integer in [a, b], in the given format.
+fn factor_range(a: u64, b: u64, format: OutputFormat) {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut start = Instant::now();
+    for (n, factors) in range::FactorRange::new(a, b) {
+        let line = match format {
+            OutputFormat::List => format!("{n} = {}", factors.get_factors_list()),
+            OutputFormat::FlatList => format!("{n} = {}", factors.get_factors_flat_list()),
+            OutputFormat::Expr => format!("{n} = {}", factors.get_factors_expr()),
+            OutputFormat::Json => {
+                let elapsed = start.elapsed();
+                start = Instant::now();
+                output::json(&BigInt::from(n), "sieve", elapsed, &factors, &[], None)
+            }
+        };
+        if writeln!(out, "{line}").is_err() {
+            return;
+        }
+    }
+    let _ = out.flush();
+}
+
+// `fr verify-cert`: lists the primes a valid certificate proves, with exit
+// status 0, or reports the first failing step with exit status 1.
+fn verify_cert(file: &str) -> i32 {
+    let text = if file == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        fs::read_to_string(file)
+    };
+    let certificate = text
+        .map_err(|e| format!("{file}: {e}"))
+        .and_then(|text| text.parse::<Certificate>())
+        .and_then(|c| c.verify().map(|()| c));
+    match certificate {
+        Ok(c) if !c.steps().is_empty() => {
+            for p in c.targets() {
+                println!("{p} is prime");
+            }
+            0
+        }
+        Ok(_) => {
+            eprintln!("invalid certificate: no steps");
+            1
+        }
+        Err(e) => {
+            eprintln!("invalid certificate: {e}");
+            1
+        }
+    }
 }
@@ -0,0 +1,327 @@
+use crate::algs::primality;
+use crate::parallel::stopped;
+use crate::sieve;
+use crate::traits::Factors;
+use num::bigint::BigInt;
+use num::integer::Roots;
+use num::traits::{WrappingMul, WrappingSub};
+use num::{Integer, PrimInt, ToPrimitive};
+use std::sync::atomic::AtomicBool;
+
+// Machine-word arithmetic for n below 2^128, so that small inputs never
+// touch BigInt.
+
+// Bases making Miller-Rabin deterministic below 3.3 * 10^24, which covers
+// every u64 and u128 up to 2^81.
+const MR_BASES: [u64; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+// Trial division bound before rho takes over.
+const TRIAL_BOUND: u64 = 1 << 10;
+
+// Differences multiplied together before taking a gcd.
+const BATCH: u64 = 128;
+
+pub trait Word:
+    PrimInt + Integer + Roots + WrappingMul + WrappingSub + Into<BigInt> + Send + Sync
+{
+    // The full product as (low, high) halves.
+    fn mul_wide(self, rhs: Self) -> (Self, Self);
+    fn from_u64(x: u64) -> Self;
+}
+
+impl Word for u64 {
+    fn mul_wide(self, rhs: Self) -> (Self, Self) {
+        let t = self as u128 * rhs as u128;
+        (t as u64, (t >> 64) as u64)
+    }
+    fn from_u64(x: u64) -> Self {
+        x
+    }
+}
+
+impl Word for u128 {
+    fn mul_wide(self, rhs: Self) -> (Self, Self) {
+        const LOW: u128 = u64::MAX as u128;
+        let (a0, a1) = (self & LOW, self >> 64);
+        let (b0, b1) = (rhs & LOW, rhs >> 64);
+        let (p00, p01, p10, p11) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+        let mid = (p00 >> 64) + (p01 & LOW) + (p10 & LOW);
+        let lo = (p00 & LOW) | (mid << 64);
+        let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+        (lo, hi)
+    }
+    fn from_u64(x: u64) -> Self {
+        x as u128
+    }
+}
+
+// Arithmetic modulo an odd n on residues in Montgomery form a R mod n, with
+// R = 2^64 or 2^128.
+#[derive(Clone, Copy)]
+pub struct Montgomery<W: Word> {
+    n: W,
+    // n^-1 mod R
+    n_inv: W,
+    // R^2 mod n
+    r2: W,
+    pub one: W,
+}
+
+impl<W: Word> Montgomery<W> {
+    pub fn new(n: W) -> Self {
+        // Newton's iteration doubles the correct low bits of n^-1 each step,
+        // starting from the 3 bits of n * n = 1 mod 8.
+        let two = W::from_u64(2);
+        let mut n_inv = n;
+        for _ in 0..6 {
+            n_inv = n_inv.wrapping_mul(&two.wrapping_sub(&n.wrapping_mul(&n_inv)));
+        }
+        let mut m = Self {
+            n,
+            n_inv,
+            r2: W::zero(),
+            one: (W::max_value() % n + W::one()) % n,
+        };
+        let mut r2 = m.one;
+        for _ in 0..W::zero().count_zeros() {
+            r2 = m.add(r2, r2);
+        }
+        m.r2 = r2;
+        m
+    }
+
+    // REDC: (lo + hi R) R^-1 mod n, for hi < n.
+    fn reduce(&self, lo: W, hi: W) -> W {
+        let m = lo.wrapping_mul(&self.n_inv);
+        let (_, mn_hi) = m.mul_wide(self.n);
+        if hi >= mn_hi {
+            hi - mn_hi
+        } else {
+            self.n - (mn_hi - hi)
+        }
+    }
+
+    pub fn to(&self, a: W) -> W {
+        self.mul(a % self.n, self.r2)
+    }
+    pub fn mul(&self, a: W, b: W) -> W {
+        let (lo, hi) = a.mul_wide(b);
+        self.reduce(lo, hi)
+    }
+    pub fn add(&self, a: W, b: W) -> W {
+        if a >= self.n - b {
+            a - (self.n - b)
+        } else {
+            a + b
+        }
+    }
+    pub fn sub(&self, a: W, b: W) -> W {
+        if a >= b {
+            a - b
+        } else {
+            a + (self.n - b)
+        }
+    }
+    pub fn pow(&self, a: W, mut e: W) -> W {
+        let (mut s, mut t) = (self.one, a);
+        while !e.is_zero() {
+            if e & W::one() == W::one() {
+                s = self.mul(s, t);
+            }
+            t = self.mul(t, t);
+            e = e >> 1;
+        }
+        s
+    }
+}
+
+// Miller-Rabin to the bases MR_BASES: a proof of primality for n below
+// 3.3 * 10^24, a strong probable prime test above.
+pub fn is_prime<W: Word>(n: W) -> bool {
+    let small = |p: u64| W::from_u64(p);
+    if n < small(2) {
+        return false;
+    }
+    for &p in MR_BASES.iter() {
+        if n % small(p) == W::zero() {
+            return n == small(p);
+        }
+    }
+    let m = Montgomery::new(n);
+    let n1 = n - W::one();
+    let s = n1.trailing_zeros();
+    let d = n1 >> s as usize;
+    let minus_one = m.sub(W::zero(), m.one);
+    MR_BASES.iter().all(|&a| {
+        let mut x = m.pow(m.to(small(a)), d);
+        if x == m.one || x == minus_one {
+            return true;
+        }
+        for _ in 1..s {
+            x = m.mul(x, x);
+            if x == minus_one {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+// Brent's rho on x -> x^2 + c for odd composite n, at most `max_iterations`
+// steps.
+pub fn brent<W: Word>(n: W, c: W, x0: W, max_iterations: u64, stop: &AtomicBool) -> Option<W> {
+    let m = Montgomery::new(n);
+    let c = m.to(c);
+    let f = |x: W| m.add(m.mul(x, x), c);
+    let diff = |a: W, b: W| if a > b { a - b } else { b - a };
+
+    let mut y = m.to(x0);
+    let mut x = y;
+    let mut ys = y;
+    let mut q = m.one;
+    let mut g = W::one();
+    let mut r: u64 = 1;
+    while g.is_one() {
+        x = y;
+        for _ in 0..r {
+            y = f(y);
+        }
+        let mut k = 0;
+        while k < r && g.is_one() {
+            if stopped(stop) {
+                return None;
+            }
+            ys = y;
+            for _ in 0..BATCH.min(r - k) {
+                y = f(y);
+                q = m.mul(q, diff(x, y));
+            }
+            g = q.gcd(&n);
+            k += BATCH;
+        }
+        if r >= max_iterations {
+            break;
+        }
+        r = r.saturating_mul(2);
+    }
+    if g == n {
+        // The batch overshot; step back through it one difference at a time.
+        loop {
+            ys = f(ys);
+            g = diff(x, ys).gcd(&n);
+            if !g.is_one() {
+                break;
+            }
+        }
+    }
+    (!g.is_one() && g != n).then_some(g)
+}
+
+// Complete factorization: trial division, then rho on what is left, trying
+// further c until each composite splits.
+pub fn factorize<W: Word>(n: W) -> Vec<(W, u32)> {
+    let mut factors: Vec<(W, u32)> = Vec::new();
+    let mut n = n;
+    for &p in sieve::primes(TRIAL_BOUND).iter() {
+        let p = W::from_u64(p);
+        if p * p > n {
+            break;
+        }
+        let mut e = 0;
+        while n % p == W::zero() {
+            n = n / p;
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((p, e));
+        }
+    }
+
+    let never = AtomicBool::new(false);
+    let mut composites = vec![n];
+    while let Some(m) = composites.pop() {
+        if m.is_one() {
+            continue;
+        }
+        if is_prime(m) {
+            match factors.iter_mut().find(|(p, _)| *p == m) {
+                Some((_, e)) => *e += 1,
+                None => factors.push((m, 1)),
+            }
+            continue;
+        }
+        let r = m.sqrt();
+        let d = if r * r == m {
+            r
+        } else {
+            (1..)
+                .find_map(|c| brent(m, W::from_u64(c), W::from_u64(2), u64::MAX, &never))
+                .unwrap()
+        };
+        composites.push(d);
+        composites.push(m / d);
+    }
+    factors.sort();
+    factors
+}
+
+// The native path for n that fits a machine word, None otherwise.
+pub fn factors(n: &BigInt) -> Option<Factors> {
+    let pairs: Vec<(BigInt, u32)> = if let Some(n) = n.to_u64() {
+        factorize(n).into_iter().map(|(p, e)| (p.into(), e)).collect()
+    } else {
+        factorize(n.to_u128()?)
+            .into_iter()
+            .map(|(p, e)| (p.into(), e))
+            .collect()
+    };
+    let mut factors = Factors::new(None);
+    for (p, e) in pairs {
+        let status = primality(&p).into();
+        factors.insert(p, e, status);
+    }
+    Some(factors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_montgomery() {
+        for n in [3u64, 1000003, u64::MAX - 58] {
+            let m = Montgomery::new(n);
+            let (a, b) = (n - 2, n / 3 + 1);
+            let ab = (a as u128 * b as u128 % n as u128) as u64;
+            assert_eq!(m.reduce(m.mul(m.to(a), m.to(b)), 0), ab);
+        }
+        let n = u128::MAX - 158;
+        let m = Montgomery::new(n);
+        assert_eq!(m.reduce(m.mul(m.to(n - 1), m.to(n - 1)), 0), 1);
+    }
+
+    #[test]
+    fn test_native_is_prime() {
+        assert!(is_prime(2u64));
+        assert!(!is_prime(1u64));
+        assert!(is_prime(u64::MAX - 58));
+        // Strong pseudoprime to the bases 2, 3, 5, 7, 11, 13 and 17.
+        assert!(!is_prime(341550071728321u64));
+        assert!(is_prime(170141183460469231731687303715884105727u128));
+        assert!(!is_prime(3825123056546413051u128 * 3));
+        // Strong pseudoprime to every base up to 37.
+        assert!(!is_prime(318665857834031151167461u128));
+    }
+
+    #[test]
+    fn test_native_factorize() {
+        assert_eq!(
+            factorize(64712443855127040u64),
+            vec![(2, 9), (3, 6), (5, 1), (7, 1), (41, 1), (9059, 1), (13337, 1)]
+        );
+        let f = factors(&bi!("3000000006470000001463", 10)).unwrap();
+        assert_eq!(f.get_factors_expr(), "10000000019 * 300000000077");
+        assert!(factors(&(BigInt::from(u128::MAX) + 1u32)).is_none());
+    }
+}
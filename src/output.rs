@@ -0,0 +1,188 @@
+use crate::certify::Certificate;
+use crate::traits::{Factors, Status};
+use num::BigInt;
+use std::time::Duration;
+
+fn status(p: &BigInt, status: Status, certificate: Option<&Certificate>) -> &'static str {
+    if certificate.is_some_and(|c| c.proves(p)) {
+        return "prime";
+    }
+    match status {
+        Status::Prime => "prime",
+        Status::ProbablePrime => "probable_prime",
+        Status::Composite => "composite",
+        Status::Unknown => "unknown",
+    }
+}
+
+// JSON document for pipelines. Big integers are decimal strings so that no
+// parser truncates them to a double. The sigmas of the ECM curves that found
+// factors are listed if there are any. A certificate, one step per array
+// element, turns the probable primes it covers into primes.
+pub fn json(
+    n: &BigInt,
+    algorithm: &str,
+    elapsed: Duration,
+    factors: &Factors,
+    sigmas: &[u64],
+    certificate: Option<&Certificate>,
+) -> String {
+    let factors_list: String = factors
+        .get_factors_vector()
+        .iter()
+        .map(|f| {
+            let (p, e) = f.pe();
+            format!(
+                r#"{{"p": "{p}", "e": {e}, "status": "{}"}}"#,
+                status(&p, f.status(), certificate)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sigma_list = if sigmas.is_empty() {
+        String::new()
+    } else {
+        let sigmas: Vec<String> = sigmas.iter().map(u64::to_string).collect();
+        format!(r#", "sigma": [{}]"#, sigmas.join(", "))
+    };
+    let certificate_list = certificate.map_or(String::new(), |c| {
+        let steps = c
+            .steps()
+            .iter()
+            .map(|s| format!(r#""{s}""#))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(r#", "certificate": [{steps}]"#)
+    });
+    format!(
+        r#"{{"n": "{n}", "algorithm": "{algorithm}", "elapsed": {:.6}, "factors": [{factors_list}]{sigma_list}{certificate_list}}}"#,
+        elapsed.as_secs_f64()
+    )
+}
+
+// A `json` document tagged with the line number and text it was read from,
+// for batch input.
+pub fn tagged(line: usize, input: &str, json: &str) -> String {
+    format!(
+        r#"{{"line": {line}, "input": "{}", {}"#,
+        escape(input),
+        &json[1..]
+    )
+}
+
+// `s` as the contents of a JSON string.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+    use crate::certify;
+
+    #[test]
+    fn test_json() {
+        let mut factors = Factors::new(None);
+        factors.add_pow(BigInt::from(3), 2);
+        factors.add(bi!("1000000000547", 10));
+        factors.add(bi!("1000000000000", 10));
+        factors.add(bi!("618970019642690137449562111", 10));
+        factors.complete();
+        factors.add(BigInt::from(10));
+        let n = factors.n();
+        assert_eq!(
+            json(&n, "auto", Duration::from_millis(1500), &factors, &[], None),
+            format!(
+                r#"{{"n": "{n}", "algorithm": "auto", "elapsed": 1.500000, "factors": [{}, {}, {}, {}, {}]}}"#,
+                r#"{"p": "3", "e": 2, "status": "prime"}"#,
+                r#"{"p": "10", "e": 1, "status": "unknown"}"#,
+                r#"{"p": "1000000000000", "e": 1, "status": "composite"}"#,
+                r#"{"p": "1000000000547", "e": 1, "status": "prime"}"#,
+                r#"{"p": "618970019642690137449562111", "e": 1, "status": "probable_prime"}"#,
+            )
+        );
+
+        let mut factors = Factors::new(None);
+        factors.add(BigInt::from(3));
+        factors.add(BigInt::from(65537));
+        factors.complete();
+        let (certificate, _) = certify::factors(&factors, 1);
+        assert_eq!(
+            json(
+                &factors.n(),
+                "trial",
+                Duration::ZERO,
+                &factors,
+                &[],
+                Some(&certificate)
+            ),
+            format!(
+                r#"{{"n": "196611", "algorithm": "trial", "elapsed": 0.000000, "factors": [{}, {}], "certificate": [{}]}}"#,
+                r#"{"p": "3", "e": 1, "status": "prime"}"#,
+                r#"{"p": "65537", "e": 1, "status": "prime"}"#,
+                r#""3 small", "2 small", "65537 pratt 3 2""#,
+            )
+        );
+
+        let mut factors = Factors::new(None);
+        factors.insert(BigInt::from(5), 1, Status::Prime);
+        factors.insert(BigInt::from(7), 1, Status::Prime);
+        assert_eq!(
+            json(
+                &BigInt::from(35),
+                "ecm",
+                Duration::ZERO,
+                &factors,
+                &[27],
+                None
+            ),
+            format!(
+                r#"{{"n": "35", "algorithm": "ecm", "elapsed": 0.000000, "factors": [{}, {}], "sigma": [27]}}"#,
+                r#"{"p": "5", "e": 1, "status": "prime"}"#,
+                r#"{"p": "7", "e": 1, "status": "prime"}"#,
+            )
+        );
+
+        let factors = Factors::new(None);
+        assert_eq!(
+            tagged(
+                3,
+                "1",
+                &json(
+                    &BigInt::from(1),
+                    "auto",
+                    Duration::ZERO,
+                    &factors,
+                    &[],
+                    None
+                )
+            ),
+            r#"{"line": 3, "input": "1", "n": "1", "algorithm": "auto", "elapsed": 0.000000, "factors": []}"#
+        );
+        assert_eq!(
+            tagged(
+                1,
+                "1\t\"\\",
+                &json(
+                    &BigInt::from(1),
+                    "auto",
+                    Duration::ZERO,
+                    &factors,
+                    &[],
+                    None
+                )
+            ),
+            r#"{"line": 1, "input": "1\u0009\"\\", "n": "1", "algorithm": "auto", "elapsed": 0.000000, "factors": []}"#
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+// Number of worker threads for a `--jobs` value; anything below 1 means all cores.
+pub fn threads(jobs: i8) -> usize {
+    if jobs < 1 {
+        thread::available_parallelism().map_or(1, |n| n.get())
+    } else {
+        jobs as usize
+    }
+}
+
+// Runs the independent attempts f(0), f(1), ..., f(tasks - 1) on `jobs`
+// threads and returns the first success. Once one attempt succeeds the flag
+// passed to `f` is raised, and attempts still running should give up.
+pub fn race<T, F>(jobs: usize, tasks: usize, f: F) -> Option<T>
+where
+    T: Send,
+    F: Fn(usize, &AtomicBool) -> Option<T> + Sync,
+{
+    let stop = AtomicBool::new(false);
+    let next = AtomicUsize::new(0);
+    let found: Mutex<Option<T>> = Mutex::new(None);
+
+    let worker = || {
+        while !stop.load(Ordering::Relaxed) {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            if i >= tasks {
+                break;
+            }
+            if let Some(r) = f(i, &stop) {
+                stop.store(true, Ordering::Relaxed);
+                found.lock().unwrap().get_or_insert(r);
+            }
+        }
+    };
+    if jobs <= 1 {
+        worker();
+    } else {
+        thread::scope(|s| {
+            for _ in 0..jobs {
+                s.spawn(worker);
+            }
+        });
+    }
+    found.into_inner().unwrap()
+}
+
+// Maps f over `items` on `jobs` threads and hands each result to `emit` in
+// input order, as soon as all earlier ones are out. Items are pulled only as
+// workers free up, so `items` may be a stream. Once `emit` returns false no
+// new item is started.
+pub fn ordered<T, R, I, F, E>(jobs: usize, items: I, f: F, mut emit: E)
+where
+    T: Send,
+    R: Send,
+    I: Iterator<Item = T> + Send,
+    F: Fn(T) -> R + Sync,
+    E: FnMut(R) -> bool,
+{
+    if jobs <= 1 {
+        for item in items {
+            if !emit(f(item)) {
+                break;
+            }
+        }
+        return;
+    }
+    let stop = AtomicBool::new(false);
+    let items = Mutex::new(items.enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|s| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (stop, items, f) = (&stop, &items, &f);
+            s.spawn(move || {
+                while !stopped(stop) {
+                    let Some((i, item)) = items.lock().unwrap().next() else {
+                        break;
+                    };
+                    if sender.send((i, f(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (i, r) in receiver {
+            pending.insert(i, r);
+            while let Some(r) = pending.remove(&next) {
+                next += 1;
+                if !emit(r) {
+                    stop.store(true, Ordering::Relaxed);
+                    return;
+                }
+            }
+        }
+    });
+}
+
+pub fn stopped(stop: &AtomicBool) -> bool {
+    stop.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_race() {
+        for jobs in [1, 4] {
+            let r = race(jobs, 1000, |i, _| if i % 97 == 96 { Some(i) } else { None });
+            assert_eq!(r.map(|i| i % 97), Some(96));
+            assert_eq!(race(jobs, 10, |_, _| None::<usize>), None);
+        }
+    }
+
+    #[test]
+    fn test_ordered() {
+        for jobs in [1, 4] {
+            let mut out = Vec::new();
+            // Later items finish first, yet come out in order.
+            ordered(
+                jobs,
+                0..20u64,
+                |i| {
+                    thread::sleep(std::time::Duration::from_millis(20 - i));
+                    i * i
+                },
+                |r| {
+                    out.push(r);
+                    true
+                },
+            );
+            assert_eq!(out, (0..20).map(|i| i * i).collect::<Vec<_>>());
+
+            let mut out = Vec::new();
+            ordered(jobs, 0..1000, |i| i, |r| {
+                out.push(r);
+                r < 9
+            });
+            assert_eq!(out, (0..10).collect::<Vec<_>>());
+        }
+    }
+}
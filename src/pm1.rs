@@ -0,0 +1,261 @@
+use crate::algs::{mod_pow, prime_power};
+use crate::driver;
+use crate::native::{Montgomery, Word};
+use crate::parallel::{race, stopped};
+use crate::sieve::{self, Sieve};
+use crate::traits::{Factorizer, Factors, Splitter};
+use num::bigint::BigInt;
+use num::{Integer, One, ToPrimitive};
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+
+pub const DEFAULT_B1: u64 = 10_000;
+pub const DEFAULT_B2: u64 = 1_000_000;
+
+// Number of stage 1 primes processed between two gcds.
+const GCD_INTERVAL: usize = 100;
+
+// Stage 1 bases; with several jobs each worker starts from its own base.
+const BASES: [u32; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+pub struct Pm1 {
+    n: BigInt,
+    b1: u64,
+    b2: u64,
+    jobs: usize,
+}
+
+impl Pm1 {
+    pub fn new(n: BigInt) -> Self {
+        Self {
+            n,
+            b1: DEFAULT_B1,
+            b2: DEFAULT_B2,
+            jobs: 1,
+        }
+    }
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+    pub fn bounds(mut self, b1: u64, b2: u64) -> Self {
+        self.b1 = b1;
+        self.b2 = b2.max(b1);
+        self
+    }
+}
+
+impl Factorizer for Pm1 {
+    fn factorize(&self) -> Option<Factors> {
+        driver::factorize(&self.n, self)
+    }
+}
+
+impl Splitter for Pm1 {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        split(n, self.b1, self.b2, self.jobs)
+    }
+}
+
+pub fn split(n: &BigInt, b1: u64, b2: u64, jobs: usize) -> Option<BigInt> {
+    if n.is_even() {
+        return Some(BigInt::from(2));
+    }
+    if let Some(m) = n.to_u64() {
+        return split_native(m, b1, b2, jobs);
+    }
+    if let Some(m) = n.to_u128() {
+        return split_native(m, b1, b2, jobs);
+    }
+    race(jobs, jobs.clamp(1, BASES.len()), |i, stop| {
+        let a = match stage1(n, &BigInt::from(BASES[i]), b1, stop) {
+            Ok(a) => a,
+            Err(d) => return d,
+        };
+        stage2(n, &a, b1, b2, stop)
+    })
+}
+
+// Raises `a` to every prime power up to `b1`. Returns the residue for stage 2
+// when no factor turned up, or the outcome of the search otherwise.
+fn stage1(
+    n: &BigInt,
+    a: &BigInt,
+    b1: u64,
+    stop: &AtomicBool,
+) -> Result<BigInt, Option<BigInt>> {
+    let ps = sieve::primes(b1);
+    let mut a = a.clone();
+
+    for chunk in ps.chunks(GCD_INTERVAL) {
+        if stopped(stop) {
+            return Err(None);
+        }
+        let checkpoint = a.clone();
+        for &p in chunk {
+            a = mod_pow(a, prime_power(p, b1), n.clone());
+        }
+        let g = (&a - 1u32).gcd(n);
+        if g.is_one() {
+            continue;
+        }
+        if g != *n {
+            return Err(Some(g));
+        }
+        // Every prime of n was found inside this chunk; redo it one prime at a time.
+        let mut a = checkpoint;
+        for &p in chunk {
+            a = mod_pow(a, prime_power(p, b1), n.clone());
+            let g = (&a - 1u32).gcd(n);
+            if !g.is_one() {
+                return Err(if g == *n { None } else { Some(g) });
+            }
+        }
+    }
+    Ok(a)
+}
+
+// Standard prime-gap continuation: walks the primes q in (b1, b2] keeping
+// a^q up to date with the precomputed powers a^gap, and accumulates the
+// product of (a^q - 1).
+fn stage2(n: &BigInt, a: &BigInt, b1: u64, b2: u64, stop: &AtomicBool) -> Option<BigInt> {
+    let mut qs = Sieve::new(b1 + 1, b2);
+    let mut q = qs.next()?;
+    let mut aq = mod_pow(a.clone(), q, n.clone());
+    let mut gaps: HashMap<u64, BigInt> = HashMap::new();
+    let mut acc = &aq - 1u32;
+
+    for (i, next) in qs.enumerate() {
+        let gap = next - q;
+        let ag = gaps
+            .entry(gap)
+            .or_insert_with(|| mod_pow(a.clone(), gap, n.clone()));
+        aq = aq * &*ag % n;
+        acc = acc * (&aq - 1u32) % n;
+        q = next;
+
+        if i % GCD_INTERVAL == 0 {
+            if stopped(stop) {
+                return None;
+            }
+            let g = acc.gcd(n);
+            if !g.is_one() {
+                return if g == *n { None } else { Some(g) };
+            }
+        }
+    }
+    let g = acc.gcd(n);
+    if g.is_one() || g == *n {
+        None
+    } else {
+        Some(g)
+    }
+}
+
+// The same two stages on machine words, with residues in Montgomery form.
+// gcd((a - 1) R mod n, n) = gcd(a - 1, n) as R is a power of 2 and n is odd.
+fn split_native<W: Word>(n: W, b1: u64, b2: u64, jobs: usize) -> Option<BigInt> {
+    let m = Montgomery::new(n);
+    race(jobs, jobs.clamp(1, BASES.len()), |i, stop| {
+        let a = match stage1_native(&m, n, m.to(W::from_u64(BASES[i].into())), b1, stop) {
+            Ok(a) => a,
+            Err(d) => return d,
+        };
+        stage2_native(&m, n, a, b1, b2, stop)
+    })
+    .map(Into::into)
+}
+
+fn stage1_native<W: Word>(
+    m: &Montgomery<W>,
+    n: W,
+    mut a: W,
+    b1: u64,
+    stop: &AtomicBool,
+) -> Result<W, Option<W>> {
+    let ps = sieve::primes(b1);
+    for chunk in ps.chunks(GCD_INTERVAL) {
+        if stopped(stop) {
+            return Err(None);
+        }
+        let checkpoint = a;
+        for &p in chunk {
+            a = m.pow(a, W::from_u64(prime_power(p, b1)));
+        }
+        let g = m.sub(a, m.one).gcd(&n);
+        if g.is_one() {
+            continue;
+        }
+        if g != n {
+            return Err(Some(g));
+        }
+        let mut a = checkpoint;
+        for &p in chunk {
+            a = m.pow(a, W::from_u64(prime_power(p, b1)));
+            let g = m.sub(a, m.one).gcd(&n);
+            if !g.is_one() {
+                return Err(if g == n { None } else { Some(g) });
+            }
+        }
+    }
+    Ok(a)
+}
+
+fn stage2_native<W: Word>(
+    m: &Montgomery<W>,
+    n: W,
+    a: W,
+    b1: u64,
+    b2: u64,
+    stop: &AtomicBool,
+) -> Option<W> {
+    let mut qs = Sieve::new(b1 + 1, b2);
+    let mut q = qs.next()?;
+    let mut aq = m.pow(a, W::from_u64(q));
+    let mut gaps: HashMap<u64, W> = HashMap::new();
+    let mut acc = m.sub(aq, m.one);
+
+    for (i, next) in qs.enumerate() {
+        let gap = next - q;
+        let ag = *gaps.entry(gap).or_insert_with(|| m.pow(a, W::from_u64(gap)));
+        aq = m.mul(aq, ag);
+        acc = m.mul(acc, m.sub(aq, m.one));
+        q = next;
+
+        if i % GCD_INTERVAL == 0 {
+            if stopped(stop) {
+                return None;
+            }
+            let g = acc.gcd(&n);
+            if !g.is_one() {
+                return if g == n { None } else { Some(g) };
+            }
+        }
+    }
+    let g = acc.gcd(&n);
+    if g.is_one() || g == n {
+        None
+    } else {
+        Some(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_pm1() {
+        // p - 1 = 2^2 * 3 * 5 * 7 * 11 * 13 * 17 * 19 * 23 * 1019 needs stage 2.
+        let n = bi!("454663269309700808176367", 10);
+        let f = Pm1::new(n.clone()).bounds(100, 2000).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "454663269061 * 1000000000547");
+        assert!(Pm1::new(n.clone()).bounds(100, 1000).split(&n).is_none());
+
+        // Above 2^128 the search runs on BigInt.
+        let n = bi!("454663269061000000000000000000000005636460546549217", 10);
+        let d = Pm1::new(n.clone()).bounds(100, 2000).split(&n).unwrap();
+        assert_eq!(d, bi!("454663269061", 10));
+    }
+}
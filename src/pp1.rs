@@ -0,0 +1,305 @@
+use crate::algs::prime_power;
+use crate::driver;
+use crate::galois_field::GaloisField;
+use crate::native::{Montgomery, Word};
+use crate::parallel::{race, stopped};
+use crate::sieve;
+use crate::traits::{Factorizer, Factors, Splitter};
+use num::bigint::BigInt;
+use num::{Integer, One, ToPrimitive};
+use num_bigint::RandBigInt;
+use rand::Rng;
+use std::sync::atomic::AtomicBool;
+
+pub const DEFAULT_B1: u64 = 10_000;
+pub const DEFAULT_B2: u64 = 1_000_000;
+pub const DEFAULT_SEEDS: usize = 3;
+
+// Number of stage 1 primes, or stage 2 giant steps, processed between two
+// gcds.
+const GCD_INTERVAL: u64 = 100;
+
+// Giant step of stage 2; every prime above B1 is m * W +/- j with j < W / 2.
+const W: u64 = 2 * 3 * 5 * 7;
+
+pub struct Pp1 {
+    n: BigInt,
+    b1: u64,
+    b2: u64,
+    seeds: usize,
+    jobs: usize,
+}
+
+impl Pp1 {
+    pub fn new(n: BigInt) -> Self {
+        Self {
+            n,
+            b1: DEFAULT_B1,
+            b2: DEFAULT_B2,
+            seeds: DEFAULT_SEEDS,
+            jobs: 1,
+        }
+    }
+    pub fn bounds(mut self, b1: u64, b2: u64) -> Self {
+        self.b1 = b1;
+        self.b2 = b2.max(b1);
+        self
+    }
+    pub fn seeds(mut self, seeds: usize) -> Self {
+        self.seeds = seeds;
+        self
+    }
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+}
+
+impl Factorizer for Pp1 {
+    fn factorize(&self) -> Option<Factors> {
+        driver::factorize(&self.n, self)
+    }
+}
+
+impl Splitter for Pp1 {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        split(n, self.b1, self.b2, self.seeds, self.jobs)
+    }
+}
+
+// Williams' p+1: each seed P0 finds p when p + 1 (or p - 1, depending on
+// whether P0^2 - 4 is a square mod p) is B1-smooth up to one prime below B2.
+pub fn split(n: &BigInt, b1: u64, b2: u64, seeds: usize, jobs: usize) -> Option<BigInt> {
+    if n.is_even() {
+        return Some(BigInt::from(2));
+    }
+    if *n <= BigInt::from(7) {
+        return None;
+    }
+    if let Some(m) = n.to_u64() {
+        return split_native(m, b1, b2, seeds, jobs);
+    }
+    if let Some(m) = n.to_u128() {
+        return split_native(m, b1, b2, seeds, jobs);
+    }
+    let f = GaloisField::GaloisField(n);
+    race(jobs, seeds, |_, stop| {
+        let mut rng = rand::thread_rng();
+        let p0 = f.new(&rng.gen_bigint_range(&BigInt::from(3), &(n - 2u32)));
+        let v = match stage1(&p0, b1, stop) {
+            Ok(v) => v,
+            Err(d) => return d,
+        };
+        stage2(&v, b1, b2, stop)
+    })
+}
+
+fn check(v: &GaloisField) -> Option<BigInt> {
+    let g = (&v.value - 2u32).gcd(&v.p);
+    if g.is_one() || g == v.p {
+        None
+    } else {
+        Some(g)
+    }
+}
+
+// V_{k}(P0) for k the product of all prime powers up to b1, using
+// V_{ab}(P) = V_a(V_b(P)).
+fn stage1(p0: &GaloisField, b1: u64, stop: &AtomicBool) -> Result<GaloisField, Option<BigInt>> {
+    let mut v = p0.clone();
+    for (i, &p) in (1..).zip(sieve::primes(b1).iter()) {
+        v = v.lucas_v(&BigInt::from(prime_power(p, b1)));
+        if i % GCD_INTERVAL == 0 {
+            if stopped(stop) {
+                return Err(None);
+            }
+            if let Some(d) = check(&v) {
+                return Err(Some(d));
+            }
+        }
+    }
+    match check(&v) {
+        Some(d) => Err(Some(d)),
+        None => Ok(v),
+    }
+}
+
+// Baby-step giant-step continuation: for q = m * W +/- j prime in (b1, b2],
+// V_{mW} - V_j vanishes mod p whenever the order of the stage 1 element
+// divides q, so the product of these differences is accumulated.
+fn stage2(v: &GaloisField, b1: u64, b2: u64, stop: &AtomicBool) -> Option<BigInt> {
+    let n = v.p.clone();
+    let mut vjs: Vec<Option<GaloisField>> = vec![None; (W / 2) as usize];
+    for j in (1..W / 2).filter(|j| j.gcd(&W) == 1) {
+        vjs[j as usize] = Some(v.lucas_v(&BigInt::from(j)));
+    }
+    let vw = v.lucas_v(&BigInt::from(W));
+
+    let m0 = b1 / W;
+    // V_{-k} = V_k, so this is also right for m0 = 0.
+    let mut prev = v.lucas_v(&BigInt::from(m0.abs_diff(1) * W));
+    let mut cur = v.lucas_v(&BigInt::from(m0 * W));
+    let mut acc = v.one();
+
+    let mut m = m0;
+    for (k, j) in sieve::giant_steps(b1 + 1, b2, W) {
+        while m < k {
+            if m % GCD_INTERVAL == GCD_INTERVAL - 1 {
+                if stopped(stop) {
+                    return None;
+                }
+                let g = acc.value.gcd(&n);
+                if !g.is_one() {
+                    return if g == n { None } else { Some(g) };
+                }
+            }
+            let next = cur.clone() * vw.clone() - prev;
+            prev = cur;
+            cur = next;
+            m += 1;
+        }
+        if let Some(vj) = &vjs[j as usize] {
+            acc = acc * (cur.clone() - vj.clone());
+        }
+    }
+    let g = acc.value.gcd(&n);
+    if g.is_one() || g == n {
+        None
+    } else {
+        Some(g)
+    }
+}
+
+// The same two stages on machine words, with residues in Montgomery form.
+fn split_native<T: Word>(n: T, b1: u64, b2: u64, seeds: usize, jobs: usize) -> Option<BigInt> {
+    let m = Montgomery::new(n);
+    race(jobs, seeds, |_, stop| {
+        let mut rng = rand::thread_rng();
+        let three = T::from_u64(3);
+        let p0 = m.to(T::from_u64(rng.gen()) % (n - three - T::one()) + three);
+        let v = match stage1_native(&m, n, p0, b1, stop) {
+            Ok(v) => v,
+            Err(d) => return d,
+        };
+        stage2_native(&m, n, v, b1, b2, stop)
+    })
+    .map(Into::into)
+}
+
+// V_k(P) by the ladder on (V_i, V_{i+1}), with V_{2i} = V_i^2 - 2 and
+// V_{2i+1} = V_i V_{i+1} - P.
+fn lucas_v_native<T: Word>(m: &Montgomery<T>, v: T, k: u64) -> T {
+    let two = m.add(m.one, m.one);
+    let (mut v0, mut v1) = (two, v);
+    for i in (0..64 - k.leading_zeros()).rev() {
+        if k >> i & 1 == 1 {
+            v0 = m.sub(m.mul(v0, v1), v);
+            v1 = m.sub(m.mul(v1, v1), two);
+        } else {
+            v1 = m.sub(m.mul(v0, v1), v);
+            v0 = m.sub(m.mul(v0, v0), two);
+        }
+    }
+    v0
+}
+
+fn check_native<T: Word>(m: &Montgomery<T>, n: T, v: T) -> Option<T> {
+    let g = m.sub(v, m.add(m.one, m.one)).gcd(&n);
+    if g.is_one() || g == n {
+        None
+    } else {
+        Some(g)
+    }
+}
+
+fn stage1_native<T: Word>(
+    m: &Montgomery<T>,
+    n: T,
+    mut v: T,
+    b1: u64,
+    stop: &AtomicBool,
+) -> Result<T, Option<T>> {
+    for (i, &p) in (1..).zip(sieve::primes(b1).iter()) {
+        v = lucas_v_native(m, v, prime_power(p, b1));
+        if i % GCD_INTERVAL == 0 {
+            if stopped(stop) {
+                return Err(None);
+            }
+            if let Some(d) = check_native(m, n, v) {
+                return Err(Some(d));
+            }
+        }
+    }
+    match check_native(m, n, v) {
+        Some(d) => Err(Some(d)),
+        None => Ok(v),
+    }
+}
+
+fn stage2_native<T: Word>(
+    m: &Montgomery<T>,
+    n: T,
+    v: T,
+    b1: u64,
+    b2: u64,
+    stop: &AtomicBool,
+) -> Option<T> {
+    let mut vjs: Vec<Option<T>> = vec![None; (W / 2) as usize];
+    for j in (1..W / 2).filter(|j| j.gcd(&W) == 1) {
+        vjs[j as usize] = Some(lucas_v_native(m, v, j));
+    }
+    let vw = lucas_v_native(m, v, W);
+
+    let m0 = b1 / W;
+    let mut prev = lucas_v_native(m, v, m0.abs_diff(1) * W);
+    let mut cur = lucas_v_native(m, v, m0 * W);
+    let mut acc = m.one;
+
+    let mut i = m0;
+    for (k, j) in sieve::giant_steps(b1 + 1, b2, W) {
+        while i < k {
+            if i % GCD_INTERVAL == GCD_INTERVAL - 1 {
+                if stopped(stop) {
+                    return None;
+                }
+                let g = acc.gcd(&n);
+                if !g.is_one() {
+                    return if g == n { None } else { Some(g) };
+                }
+            }
+            let next = m.sub(m.mul(cur, vw), prev);
+            prev = cur;
+            cur = next;
+            i += 1;
+        }
+        if let Some(vj) = vjs[j as usize] {
+            acc = m.mul(acc, m.sub(cur, vj));
+        }
+    }
+    let g = acc.gcd(&n);
+    if g.is_one() || g == n {
+        None
+    } else {
+        Some(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_pp1() {
+        // p + 1 = 2^4 * 3^2 * 5 * 7 * 11 * 13 * 17 * 19 * 1087 needs stage 2.
+        // Each seed succeeds with probability 1/2.
+        let n = bi!("253045512857415895457293", 10);
+        let f = Pp1::new(n).bounds(100, 2000).seeds(20).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "253045512719 * 1000000000547");
+
+        // Above 2^128 the search runs on BigInt.
+        let n = bi!("253045512719000000000000000000000003137005221177443", 10);
+        let d = Pp1::new(n.clone()).bounds(100, 2000).seeds(20).split(&n).unwrap();
+        assert_eq!(d, bi!("253045512719", 10));
+    }
+}
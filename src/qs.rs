@@ -0,0 +1,440 @@
+use crate::algs::{mod_pow_u64, perfect_power, sqrt_mod};
+use crate::driver;
+use crate::linalg::{Relation, Relations};
+use crate::rho;
+use crate::sieve;
+use crate::traits::{Factorizer, Factors, Splitter};
+use num::bigint::BigInt;
+use num::{Integer, Signed, ToPrimitive, Zero};
+use rand::Rng;
+use std::collections::HashSet;
+
+// Sieve block, sized to stay in L1/L2 cache.
+const BLOCK: usize = 1 << 15;
+
+// Primes below this are not sieved; their contribution is made up for in the
+// threshold and they are found again by trial division.
+const SMALL_PRIME: u64 = 30;
+
+// Partial relations are kept when the cofactor is below LARGE_PRIME times the
+// largest factor base prime.
+const LARGE_PRIME: u64 = 64;
+
+// Bits below the expected size of a smooth value at which a sieve cell is
+// still trial divided, covering the unsieved small primes and rounding.
+const THRESHOLD_SLACK: f64 = 14.0;
+
+// Relations collected beyond the number of matrix columns.
+const EXTRA_RELATIONS: usize = 64;
+
+// (digits, factor base size, sieve blocks per polynomial)
+const PARAMS: [(usize, usize, usize); 8] = [
+    (30, 150, 1),
+    (40, 400, 1),
+    (50, 1200, 1),
+    (60, 3000, 2),
+    (70, 6000, 4),
+    (80, 12000, 6),
+    (90, 24000, 8),
+    (100, 40000, 12),
+];
+
+const MULTIPLIERS: [u64; 20] = [
+    1, 3, 5, 7, 11, 13, 15, 17, 19, 21, 23, 29, 31, 33, 35, 37, 39, 41, 43, 47,
+];
+
+pub struct QS {
+    n: BigInt,
+}
+
+impl QS {
+    pub fn new(n: BigInt) -> Self {
+        Self { n }
+    }
+}
+
+impl Factorizer for QS {
+    fn factorize(&self) -> Option<Factors> {
+        driver::factorize(&self.n, self)
+    }
+}
+
+impl Splitter for QS {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        split(n)
+    }
+}
+
+// Self-initializing quadratic sieve. Relations (Ax + B)^2 = A g(x) mod kn with
+// g(x) smooth over the factor base (up to one large prime) are combined into
+// a congruence of squares by linear algebra over GF(2).
+pub fn split(n: &BigInt) -> Option<BigInt> {
+    if n.is_even() {
+        return Some(BigInt::from(2));
+    }
+    if let Some((r, _)) = perfect_power(n) {
+        return Some(r);
+    }
+    // Numbers this small are not worth setting up a sieve for.
+    if n.bits() < 64 {
+        return rho::split(n, u64::MAX, 1);
+    }
+
+    let k = multiplier(n);
+    let kn = n * k;
+    let digits = n.to_string().len();
+    let &(_, fb_size, blocks) = PARAMS
+        .iter()
+        .find(|&&(d, _, _)| d >= digits)
+        .unwrap_or(&PARAMS[PARAMS.len() - 1]);
+    let fb = match FactorBase::new(n, &kn, fb_size) {
+        Ok(fb) => fb,
+        Err(p) => return Some(p),
+    };
+    let mut sieve = Sieve::new(&fb, &kn, blocks);
+
+    let mut relations = Relations::new();
+    let mut target = fb.primes.len() + 1 + EXTRA_RELATIONS;
+    loop {
+        while relations.count() < target {
+            for r in sieve.next_polynomials(&fb) {
+                relations.add(r, n);
+            }
+        }
+        if let Some(d) = relations.factor(&fb.primes, n) {
+            return Some(d);
+        }
+        target += EXTRA_RELATIONS;
+    }
+}
+
+// Knuth-Schroeppel: the multiplier k making small primes most likely to
+// divide values of the polynomials for kn.
+pub fn multiplier(n: &BigInt) -> u64 {
+    let score = |k: u64| {
+        let kn = n * k;
+        let mut s = -0.5 * (k as f64).ln();
+        s += match (&kn % 8u32).to_u64().unwrap() {
+            1 => 2.0,
+            5 => 1.0,
+            _ => 0.5,
+        } * 2f64.ln();
+        for &p in sieve::primes(1000)[1..].iter() {
+            let lp = (p as f64).ln();
+            let r = (&kn % p).to_u64().unwrap();
+            if r == 0 {
+                s += lp / p as f64;
+            } else if mod_pow_u64(r, (p - 1) / 2, p) == 1 {
+                s += 2.0 * lp / (p - 1) as f64;
+            }
+        }
+        s
+    };
+    MULTIPLIERS
+        .iter()
+        .copied()
+        .map(|k| (k, score(k)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap()
+        .0
+}
+
+struct FactorBase {
+    primes: Vec<u64>,
+    // A square root of kn modulo each prime.
+    roots: Vec<u64>,
+    logs: Vec<u8>,
+}
+
+impl FactorBase {
+    // The primes modulo which kn is a square. A prime dividing n itself is
+    // returned as Err.
+    fn new(n: &BigInt, kn: &BigInt, size: usize) -> Result<Self, BigInt> {
+        let mut fb = Self {
+            primes: Vec::with_capacity(size),
+            roots: Vec::with_capacity(size),
+            logs: Vec::with_capacity(size),
+        };
+        for p in sieve::Sieve::new(2, u64::MAX) {
+            if fb.primes.len() == size {
+                break;
+            }
+            if (n % p).is_zero() {
+                return Err(BigInt::from(p));
+            }
+            if let Some(r) = sqrt_mod((kn % p).to_u64().unwrap(), p) {
+                fb.primes.push(p);
+                fb.roots.push(r);
+                fb.logs.push((p as f64).log2().round() as u8);
+            }
+        }
+        Ok(fb)
+    }
+}
+
+// Polynomial state: A = q_1 ... q_s, B = +/-B_1 +/- ... +/- B_s, and the
+// roots of g(x) = ((Ax + B)^2 - kn) / A modulo every sieved prime.
+struct Sieve {
+    kn: BigInt,
+    m: usize,
+    blocks: usize,
+    threshold: u8,
+    large_bound: u64,
+    // Indices of the factor base primes A may be built from, and how many.
+    window: Vec<usize>,
+    s: usize,
+    ln_target: f64,
+    used: HashSet<Vec<usize>>,
+    array: Vec<u8>,
+}
+
+impl Sieve {
+    fn new(fb: &FactorBase, kn: &BigInt, blocks: usize) -> Self {
+        let m = blocks * BLOCK / 2;
+        let kn_f = kn.to_f64().unwrap();
+        let p_max = fb.primes[fb.primes.len() - 1];
+        let large_bound = p_max * LARGE_PRIME;
+
+        // |g(x)| is about M sqrt(kn / 2) at the ends of the interval.
+        let log_g = (m as f64).log2() + 0.5 * (kn_f / 2.0).log2();
+        let threshold =
+            (log_g - (large_bound as f64).log2() - THRESHOLD_SLACK).clamp(1.0, 127.0) as u8;
+
+        // A should be about sqrt(2 kn) / M, with factors of a few thousand
+        // but no larger than the bulk of the factor base.
+        let ln_target = 0.5 * (2.0 * kn_f).ln() - (m as f64).ln();
+        let first = fb.primes.partition_point(|&p| p < SMALL_PRIME);
+        let cap = fb.primes[first.max(fb.primes.len() * 3 / 4)] as f64;
+        let mut s = ((ln_target / 2000f64.ln()).round() as usize).max(2);
+        while (ln_target / s as f64).exp() > cap {
+            s += 1;
+        }
+        let q = (ln_target / s as f64).exp();
+        let mut lo = fb.primes.partition_point(|&p| (p as f64) < q / 2.0).max(first);
+        let mut hi = fb.primes.partition_point(|&p| (p as f64) < q * 2.0);
+        while hi - lo < s + 8 && (lo > first || hi < fb.primes.len()) {
+            lo = lo.saturating_sub(1).max(first);
+            hi = (hi + 1).min(fb.primes.len());
+        }
+
+        Self {
+            kn: kn.clone(),
+            m,
+            blocks,
+            threshold,
+            large_bound,
+            window: (lo..hi).collect(),
+            s,
+            ln_target,
+            used: HashSet::new(),
+            array: vec![0; BLOCK],
+        }
+    }
+
+    // A fresh A: s - 1 random primes from the window, and a last one
+    // bringing the product closest to the target.
+    fn choose_a(&mut self, fb: &FactorBase) -> Vec<usize> {
+        let mut rng = rand::thread_rng();
+        loop {
+            let mut qs: Vec<usize> = Vec::with_capacity(self.s);
+            while qs.len() < self.s - 1 {
+                let i = self.window[rng.gen_range(0..self.window.len())];
+                if !qs.contains(&i) {
+                    qs.push(i);
+                }
+            }
+            let ln_prod: f64 = qs.iter().map(|&i| (fb.primes[i] as f64).ln()).sum();
+            let last = (self.ln_target - ln_prod).exp();
+            let first = fb.primes.partition_point(|&p| p < SMALL_PRIME);
+            let best = (first..fb.primes.len())
+                .filter(|i| !qs.contains(i))
+                .min_by(|&i, &j| {
+                    let di = (fb.primes[i] as f64 - last).abs();
+                    let dj = (fb.primes[j] as f64 - last).abs();
+                    di.total_cmp(&dj)
+                })
+                .unwrap();
+            qs.push(best);
+            qs.sort_unstable();
+            if self.used.insert(qs.clone()) {
+                return qs;
+            }
+        }
+    }
+
+    // Sieves all 2^(s-1) polynomials for a new A and returns the relations found.
+    fn next_polynomials(&mut self, fb: &FactorBase) -> Vec<Relation> {
+        let qs = self.choose_a(fb);
+        let a: BigInt = qs.iter().map(|&i| BigInt::from(fb.primes[i])).product();
+
+        // B_l = (A / q_l) * (sqrt(kn) * (A / q_l)^-1 mod q_l), so that
+        // B^2 = kn mod A for every choice of signs.
+        let bs: Vec<BigInt> = qs
+            .iter()
+            .map(|&i| {
+                let q = fb.primes[i];
+                let aq = &a / q;
+                let aq_inv = mod_pow_u64((&aq % q).to_u64().unwrap(), q - 2, q);
+                let mut gamma = fb.roots[i] * aq_inv % q;
+                if gamma > q / 2 {
+                    gamma = q - gamma;
+                }
+                aq * gamma
+            })
+            .collect();
+        let b: BigInt = bs.iter().sum();
+
+        // The roots of g(x) modulo each sieved prime, as offsets into the
+        // sieve interval [-M, M), and 2 B_l A^-1 mod p for every l.
+        let count = fb.primes.len();
+        let sieved: Vec<usize> = (0..count)
+            .filter(|&j| fb.primes[j] >= SMALL_PRIME && !qs.contains(&j))
+            .filter(|&j| !(&self.kn % fb.primes[j]).is_zero())
+            .collect();
+        let mut bainv = vec![vec![0u64; count]; bs.len()];
+        let mut offsets = vec![(0u64, 0u64); count];
+        for &j in &sieved {
+            let p = fb.primes[j];
+            let ainv = mod_pow_u64((&a % p).to_u64().unwrap(), p - 2, p);
+            for (l, bl) in bs.iter().enumerate() {
+                bainv[l][j] = 2 * (bl % p).to_u64().unwrap() * ainv % p;
+            }
+            let bp = (&b % p).to_u64().unwrap();
+            let t = fb.roots[j];
+            let m = self.m as u64 % p;
+            offsets[j] = (
+                ((t + p - bp) * ainv + m) % p,
+                ((2 * p - t - bp) * ainv + m) % p,
+            );
+        }
+
+        let mut poly = Polynomial { qs, a, b, c: BigInt::zero() };
+        let mut relations = Vec::new();
+        for i in 0..1usize << (poly.qs.len() - 1) {
+            if i > 0 {
+                // Gray code: flip the sign of B_v, moving every root by
+                // -/+ 2 B_v A^-1.
+                let v = i.trailing_zeros() as usize;
+                let negate = (i >> v) & 2 == 0;
+                if negate {
+                    poly.b -= &bs[v] * 2u32;
+                } else {
+                    poly.b += &bs[v] * 2u32;
+                }
+                for &j in &sieved {
+                    let p = fb.primes[j];
+                    let d = if negate { bainv[v][j] } else { p - bainv[v][j] };
+                    let (o1, o2) = &mut offsets[j];
+                    *o1 = if *o1 + d >= p { *o1 + d - p } else { *o1 + d };
+                    *o2 = if *o2 + d >= p { *o2 + d - p } else { *o2 + d };
+                }
+            }
+            poly.c = (&poly.b * &poly.b - &self.kn) / &poly.a;
+            self.sieve(fb, &sieved, &offsets, &poly, &mut relations);
+        }
+        relations
+    }
+
+    fn sieve(
+        &mut self,
+        fb: &FactorBase,
+        sieved: &[usize],
+        offsets: &[(u64, u64)],
+        poly: &Polynomial,
+        relations: &mut Vec<Relation>,
+    ) {
+        let mut next = offsets.to_vec();
+        for block in 0..self.blocks {
+            let start = (block * BLOCK) as u64;
+            let end = start + BLOCK as u64;
+            self.array.fill(0x80 - self.threshold);
+            for &j in sieved {
+                let p = fb.primes[j];
+                let log = fb.logs[j];
+                let (n1, n2) = &mut next[j];
+                for n in [n1, n2] {
+                    while *n < end {
+                        let cell = &mut self.array[(*n - start) as usize];
+                        *cell = cell.wrapping_add(log);
+                        *n += p;
+                    }
+                }
+            }
+            // Cells start at 0x80 - threshold, so a set high bit marks a candidate.
+            for w in (0..BLOCK).step_by(8) {
+                let word = u64::from_le_bytes(self.array[w..w + 8].try_into().unwrap());
+                if word & 0x8080_8080_8080_8080 == 0 {
+                    continue;
+                }
+                for i in (w..w + 8).filter(|&i| self.array[i] & 0x80 != 0) {
+                    let index = start + i as u64;
+                    if let Some(r) = self.check(fb, offsets, poly, index) {
+                        relations.push(r);
+                    }
+                }
+            }
+        }
+    }
+
+    // Trial divides g(x) over the factor base, x = index - M.
+    fn check(
+        &self,
+        fb: &FactorBase,
+        offsets: &[(u64, u64)],
+        poly: &Polynomial,
+        index: u64,
+    ) -> Option<Relation> {
+        let x = BigInt::from(index as i64 - self.m as i64);
+        let mut g = (&poly.a * &x + &poly.b * 2u32) * &x + &poly.c;
+        if g.is_zero() {
+            return None;
+        }
+        // A g(x) contributes every prime of A once.
+        let mut factors: Vec<usize> = poly.qs.iter().map(|&j| j + 1).collect();
+        if g.is_negative() {
+            factors.push(0);
+            g = -g;
+        }
+        for (j, (&p, &(o1, o2))) in fb.primes.iter().zip(offsets).enumerate() {
+            // Primes that are not sieved have no offsets and are tried directly.
+            let r = index % p;
+            if o1 != o2 && r != o1 && r != o2 {
+                continue;
+            }
+            while (&g % p).is_zero() {
+                g /= p;
+                factors.push(j + 1);
+            }
+        }
+        let large = g.to_u64().filter(|&l| l < self.large_bound)?;
+        Some(Relation {
+            y: &poly.a * x + &poly.b,
+            factors,
+            large,
+        })
+    }
+}
+
+// The polynomial (Ax + B)^2 - kn = A g(x) with g(x) = Ax^2 + 2Bx + C.
+struct Polynomial {
+    qs: Vec<usize>,
+    a: BigInt,
+    b: BigInt,
+    c: BigInt,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_qs() {
+        let n = bi!("1000000000000000000117", 10) * bi!("1000000000000000000193", 10);
+        let f = QS::new(n).factorize().unwrap();
+        assert_eq!(
+            f.get_factors_expr(),
+            "P?1000000000000000000117 * P?1000000000000000000193"
+        );
+    }
+}
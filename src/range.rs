@@ -0,0 +1,128 @@
+use crate::auto::Auto;
+use crate::sieve;
+use crate::traits::{Factorizer, Factors, Status};
+use num::bigint::BigInt;
+use std::vec::IntoIter;
+
+// Integers factored per segment.
+const SEGMENT: u64 = 1 << 16;
+
+// Largest prime divided out by the sieve. What is left of an integer above
+// SIEVE_BOUND^2 goes to the general pipeline.
+const SIEVE_BOUND: u64 = 1 << 16;
+
+// The factorizations of the integers in [lo, hi], in order, for lo >= 1. Each segment is
+// walked once per sieving prime p, dividing p out of its multiples only, so
+// the cost per integer is about log log of the bound instead of a trial
+// division per prime.
+pub struct FactorRange {
+    lo: u64,
+    hi: u64,
+    done: bool,
+    buffer: IntoIter<(u64, Factors)>,
+}
+
+impl FactorRange {
+    pub fn new(lo: u64, hi: u64) -> Self {
+        assert!(lo >= 1, "0 has no factorization");
+        Self {
+            lo,
+            hi,
+            done: lo > hi,
+            buffer: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl Iterator for FactorRange {
+    type Item = (u64, Factors);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.buffer.next() {
+                return Some(item);
+            }
+            if self.done {
+                return None;
+            }
+            // Inclusive, so that the range can end at u64::MAX.
+            let hi = self.hi.min(self.lo.saturating_add(SEGMENT - 1));
+            self.buffer = segment(self.lo, hi).into_iter();
+            self.done = hi == self.hi;
+            self.lo = hi.wrapping_add(1);
+        }
+    }
+}
+
+// The factorizations of the integers in [lo, hi].
+fn segment(lo: u64, hi: u64) -> Vec<(u64, Factors)> {
+    let mut rest: Vec<u64> = (lo..=hi).collect();
+    let mut factors: Vec<Factors> = (lo..=hi).map(|_| Factors::new(None)).collect();
+    let bound = hi.isqrt().min(SIEVE_BOUND);
+    for &p in sieve::primes(bound).iter() {
+        let Some(first) = lo.div_ceil(p).checked_mul(p) else {
+            continue;
+        };
+        for m in (first..=hi).step_by(p as usize) {
+            let i = (m - lo) as usize;
+            let mut e = 0;
+            while rest[i].is_multiple_of(p) {
+                rest[i] /= p;
+                e += 1;
+            }
+            factors[i].insert(BigInt::from(p), e, Status::Prime);
+        }
+    }
+
+    // A cofactor with no prime factor up to the bound is prime below
+    // (bound + 1)^2.
+    let square = (bound + 1) * (bound + 1);
+    for (m, f) in rest.into_iter().zip(factors.iter_mut()) {
+        if m == 1 {
+            continue;
+        }
+        if m < square {
+            f.insert(BigInt::from(m), 1, Status::Prime);
+            continue;
+        }
+        match Auto::new(BigInt::from(m)).factorize() {
+            Some(g) => f.merge(g),
+            None => f.add(BigInt::from(m)),
+        }
+    }
+    (lo..=hi).zip(factors).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algs::is_prime;
+    use crate::trial::Trial;
+
+    #[test]
+    fn test_factor_range() {
+        let exprs: Vec<String> = FactorRange::new(1, 12)
+            .map(|(n, f)| format!("{n} = {}", f.get_factors_expr()))
+            .collect();
+        assert_eq!(exprs[0], "1 = ");
+        assert_eq!(exprs[11], "12 = 2^2 * 3");
+        assert_eq!(exprs.len(), 12);
+
+        // Across segments, against trial division, and past SIEVE_BOUND^2.
+        for lo in [SEGMENT - 50, (1 << 33) - 50, u64::MAX - 99] {
+            let hi = lo + 99;
+            let mut count = 0;
+            for (n, f) in FactorRange::new(lo, hi) {
+                if n < 1 << 40 {
+                    let expected = Trial::new(BigInt::from(n)).factorize().unwrap();
+                    assert_eq!(f.get_factors_expr(), expected.get_factors_expr());
+                }
+                assert_eq!(f.n(), BigInt::from(n));
+                assert!(f.get_factors().keys().all(is_prime));
+                count += 1;
+            }
+            assert_eq!(count, 100);
+        }
+        assert_eq!(FactorRange::new(u64::MAX, u64::MAX).map(|(n, _)| n).collect::<Vec<_>>(), [u64::MAX]);
+        assert_eq!(FactorRange::new(2, 1).count(), 0);
+    }
+}
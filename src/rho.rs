@@ -0,0 +1,155 @@
+use crate::driver;
+use crate::native::{self, Word};
+use crate::parallel::{race, stopped};
+use crate::traits::{Factorizer, Factors, Splitter};
+use num::bigint::BigInt;
+use num::{Integer, One, Signed, ToPrimitive};
+use num_bigint::RandBigInt;
+use rand::Rng;
+use std::cmp::min;
+use std::sync::atomic::AtomicBool;
+
+// Number of differences multiplied together before taking a gcd.
+const BATCH: u64 = 128;
+
+// Number of random polynomials tried before giving up.
+const ATTEMPTS: usize = 8;
+
+pub struct Rho {
+    n: BigInt,
+    jobs: usize,
+}
+
+impl Rho {
+    pub fn new(n: BigInt) -> Self {
+        Self { n, jobs: 1 }
+    }
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+}
+
+impl Factorizer for Rho {
+    fn factorize(&self) -> Option<Factors> {
+        driver::factorize(&self.n, self)
+    }
+}
+
+impl Splitter for Rho {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        split(n, u64::MAX, self.jobs)
+    }
+}
+
+// Brent's variant of Pollard's rho. Each attempt walks x -> x^2 + c with its
+// own random c for at most `max_iterations` steps; `jobs` attempts run at once.
+pub fn split(n: &BigInt, max_iterations: u64, jobs: usize) -> Option<BigInt> {
+    if n.is_even() {
+        return Some(BigInt::from(2));
+    }
+    if *n <= BigInt::from(3) {
+        return None;
+    }
+    if let Some(m) = n.to_u64() {
+        return split_native(m, max_iterations, jobs);
+    }
+    if let Some(m) = n.to_u128() {
+        return split_native(m, max_iterations, jobs);
+    }
+    let low = BigInt::one();
+    let high = n - 2u32;
+
+    race(jobs, ATTEMPTS, |_, stop| {
+        let mut rng = rand::thread_rng();
+        let c = rng.gen_bigint_range(&low, &high);
+        let x0 = rng.gen_bigint_range(&low, &high);
+        brent(n, &c, x0, max_iterations, stop)
+    })
+}
+
+fn split_native<W: Word>(n: W, max_iterations: u64, jobs: usize) -> Option<BigInt> {
+    race(jobs, ATTEMPTS, |_, stop| {
+        let mut rng = rand::thread_rng();
+        let c = W::from_u64(rng.gen()) % n;
+        let x0 = W::from_u64(rng.gen()) % n;
+        native::brent(n, c, x0, max_iterations, stop)
+    })
+    .map(Into::into)
+}
+
+fn brent(
+    n: &BigInt,
+    c: &BigInt,
+    x0: BigInt,
+    max_iterations: u64,
+    stop: &AtomicBool,
+) -> Option<BigInt> {
+    let f = |x: &BigInt| (x * x + c) % n;
+
+    let mut y = x0;
+    let mut x = y.clone();
+    let mut ys = y.clone();
+    let mut q = BigInt::one();
+    let mut g = BigInt::one();
+    let mut r: u64 = 1;
+
+    while g.is_one() {
+        x = y.clone();
+        for i in 0..r {
+            if i % BATCH == 0 && stopped(stop) {
+                return None;
+            }
+            y = f(&y);
+        }
+        let mut k = 0;
+        while k < r && g.is_one() {
+            if stopped(stop) {
+                return None;
+            }
+            ys = y.clone();
+            for _ in 0..min(BATCH, r - k) {
+                y = f(&y);
+                q = q * (&x - &y).abs() % n;
+            }
+            g = q.gcd(n);
+            k += BATCH;
+        }
+        if r >= max_iterations {
+            break;
+        }
+        r = r.saturating_mul(2);
+    }
+
+    if g == *n {
+        // The batch overshot; step back through it one difference at a time.
+        loop {
+            ys = f(&ys);
+            g = (&x - &ys).abs().gcd(n);
+            if !g.is_one() {
+                break;
+            }
+        }
+    }
+
+    if g.is_one() || g == *n {
+        None
+    } else {
+        Some(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+    use num::bigint::BigInt;
+
+    #[test]
+    fn test_rho() {
+        let n = bi!("3000000006470000001463", 10);
+        let f = Rho::new(n.clone()).factorize().unwrap();
+        assert_eq!(f.n(), n);
+        assert_eq!(f.get_factors_expr(), "10000000019 * 300000000077");
+    }
+}
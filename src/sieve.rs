@@ -0,0 +1,256 @@
+use crate::native;
+use std::ops::Deref;
+use std::sync::{Arc, LazyLock, RwLock};
+
+// Primes from a segmented sieve of Eratosthenes, either streamed over a range
+// or read from a shared table that grows on demand.
+
+// Odd numbers per segment, one byte each.
+const SEGMENT: u64 = 1 << 15;
+
+// Bound of the first table the cache builds.
+const MIN_CACHE: u64 = 1 << 16;
+
+// Largest base prime a streamed segment is sieved with. Beyond BASE_BOUND^2
+// the survivors are confirmed by Miller-Rabin instead.
+const BASE_BOUND: u64 = 1 << 24;
+
+// The primes up to `limit`.
+struct Cache {
+    limit: u64,
+    primes: Arc<Vec<u64>>,
+}
+
+static CACHE: LazyLock<RwLock<Cache>> = LazyLock::new(|| {
+    RwLock::new(Cache {
+        limit: 1,
+        primes: Arc::new(Vec::new()),
+    })
+});
+
+// The primes up to some bound, as a slice of the shared table.
+#[derive(Clone)]
+pub struct Primes {
+    all: Arc<Vec<u64>>,
+    len: usize,
+}
+
+impl Deref for Primes {
+    type Target = [u64];
+    fn deref(&self) -> &[u64] {
+        &self.all[..self.len]
+    }
+}
+
+// The primes up to `bound`, growing the shared table to at least twice its
+// previous bound when it falls short.
+pub fn primes(bound: u64) -> Primes {
+    let slice = |all: &Arc<Vec<u64>>| Primes {
+        all: all.clone(),
+        len: all.partition_point(|&p| p <= bound),
+    };
+    {
+        let cache = CACHE.read().unwrap();
+        if bound <= cache.limit {
+            return slice(&cache.primes);
+        }
+    }
+    let mut cache = CACHE.write().unwrap();
+    if bound > cache.limit {
+        let limit = bound.max(cache.limit.saturating_mul(2)).max(MIN_CACHE);
+        let base = simple(limit.isqrt());
+        let mut all = cache.primes.to_vec();
+        let mut lo = cache.limit + 1;
+        loop {
+            let hi = limit.min(lo.saturating_add(2 * SEGMENT - 1));
+            all.extend(segment(lo, hi, &base));
+            if hi == limit {
+                break;
+            }
+            lo = hi + 1;
+        }
+        *cache = Cache {
+            limit,
+            primes: Arc::new(all),
+        };
+    }
+    slice(&cache.primes)
+}
+
+// The primes in [lo, hi] in increasing order, sieved a segment at a time with
+// base primes from the shared table.
+pub struct Sieve {
+    lo: u64,
+    hi: u64,
+    done: bool,
+    buffer: std::vec::IntoIter<u64>,
+}
+
+impl Sieve {
+    pub fn new(lo: u64, hi: u64) -> Self {
+        Self {
+            lo,
+            hi,
+            done: lo > hi,
+            buffer: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl Iterator for Sieve {
+    type Item = u64;
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            if let Some(p) = self.buffer.next() {
+                return Some(p);
+            }
+            if self.done {
+                return None;
+            }
+            // Inclusive, so that the range can end at u64::MAX.
+            let hi = self.hi.min(self.lo.saturating_add(2 * SEGMENT - 1));
+            let root = hi.isqrt();
+            let base = primes(root.min(BASE_BOUND));
+            let mut found = segment(self.lo, hi, &base);
+            if root > BASE_BOUND {
+                found.retain(|&p| native::is_prime(p));
+            }
+            self.buffer = found.into_iter();
+            self.done = hi == self.hi;
+            self.lo = hi.wrapping_add(1);
+        }
+    }
+}
+
+// The primes q in [lo, hi] as pairs (m, j) with q = m * d +/- j and j <= d / 2,
+// for baby-step giant-step continuations; m never decreases.
+pub fn giant_steps(lo: u64, hi: u64, d: u64) -> impl Iterator<Item = (u64, u64)> {
+    Sieve::new(lo, hi).map(move |q| {
+        let m = (q + d / 2) / d;
+        (m, q.abs_diff(m * d))
+    })
+}
+
+// The primes in [lo, hi], crossing off the odd numbers with the odd primes
+// of `base`, which must reach sqrt(hi) for the result to be exact.
+fn segment(lo: u64, hi: u64, base: &[u64]) -> Vec<u64> {
+    let mut found = Vec::new();
+    if lo <= 2 && 2 <= hi {
+        found.push(2);
+    }
+    let first = lo.max(3) | 1;
+    if first > hi {
+        return found;
+    }
+    let mut composite = vec![false; ((hi - first) / 2 + 1) as usize];
+    for &p in base.iter().skip_while(|&&p| p == 2) {
+        if p > hi / p {
+            break;
+        }
+        // first + 2i is the first odd multiple of p for i = -first / 2 mod p.
+        let i = (p - first % p) % p * p.div_ceil(2) % p;
+        let i = i.max(p * p / 2 - first.min(p * p) / 2);
+        for i in (i as usize..composite.len()).step_by(p as usize) {
+            composite[i] = true;
+        }
+    }
+    found.extend(
+        composite
+            .iter()
+            .enumerate()
+            .filter(|(_, &c)| !c)
+            .map(|(i, _)| first + 2 * i as u64),
+    );
+    found
+}
+
+// The number of primes up to x, by Lucy_Hedgehog's method: S(v) counts the
+// integers in [2, v] left after sieving by the primes below p, and only the
+// O(sqrt(x)) values v = x / k matter. Sieving by p removes S(v / p) - S(p - 1)
+// from each S(v) with v >= p^2, in O(x^(3/4)) steps overall.
+pub fn pi(x: u64) -> u64 {
+    if x < 2 {
+        return 0;
+    }
+    let r = x.isqrt();
+    // small[v] = S(v) for v <= r, and large[k] = S(x / k) for 1 <= k <= r.
+    let mut small: Vec<u64> = (0..=r).map(|v| v.saturating_sub(1)).collect();
+    let mut large: Vec<u64> = (0..=r).map(|k| (x / k.max(1)).saturating_sub(1)).collect();
+    for p in 2..=r {
+        let (p, below) = (p as usize, small[p as usize - 1]);
+        if small[p] == below {
+            continue;
+        }
+        let square = (p * p) as u64;
+        for k in 1..=(x / square).min(r) as usize {
+            let d = k * p;
+            let s = if d as u64 <= r {
+                large[d]
+            } else {
+                small[(x / d as u64) as usize]
+            };
+            large[k] -= s - below;
+        }
+        for v in (square as usize..=r as usize).rev() {
+            small[v] -= small[v / p] - below;
+        }
+    }
+    large[1]
+}
+
+// The primes up to `bound` by the plain sieve, for the base of the table.
+fn simple(bound: u64) -> Vec<u64> {
+    let mut composite = vec![false; bound as usize + 1];
+    let mut found = Vec::new();
+    for p in 2..=bound {
+        if composite[p as usize] {
+            continue;
+        }
+        found.push(p);
+        for m in (p * p..=bound).step_by(p as usize) {
+            composite[m as usize] = true;
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sieve() {
+        assert_eq!(simple(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(segment(0, 30, &simple(5)), simple(30));
+        assert_eq!(segment(10, 12, &simple(3)), [11]);
+
+        assert_eq!(primes(100).len(), 25);
+        assert_eq!(primes(1_000_000).len(), 78498);
+        assert_eq!(primes(10)[..], [2, 3, 5, 7]);
+        assert_eq!(primes(99991).last(), Some(&99991));
+
+        // Across segment boundaries, and far from the table.
+        assert_eq!(Sieve::new(0, 1_000_000).count(), 78498);
+        assert!(Sieve::new(0, 1_000_000).eq(primes(1_000_000).iter().copied()));
+        let p: Vec<u64> = Sieve::new(1_000_000_000_000, 1_000_000_000_100).collect();
+        assert_eq!(p, [1_000_000_000_039, 1_000_000_000_061, 1_000_000_000_063, 1_000_000_000_091]);
+        let p: Vec<u64> = Sieve::new(u64::MAX - 100, u64::MAX).collect();
+        assert_eq!(p, [u64::MAX - 94, u64::MAX - 82, u64::MAX - 58]);
+        assert_eq!(Sieve::new(u64::MAX - 58, u64::MAX - 58).collect::<Vec<_>>(), [u64::MAX - 58]);
+        assert_eq!(Sieve::new(11, 11).collect::<Vec<_>>(), [11]);
+        assert_eq!(Sieve::new(12, 11).count(), 0);
+
+        // 311 = 210 + 101, 313 = 210 + 103 and 317 = 420 - 103.
+        let steps: Vec<(u64, u64)> = giant_steps(310, 320, 210).collect();
+        assert_eq!(steps, [(1, 101), (1, 103), (2, 103)]);
+    }
+
+    #[test]
+    fn test_pi() {
+        for x in [0, 1, 2, 3, 4, 10, 99, 100, 101, 9973, 65536, 1_000_000] {
+            assert_eq!(pi(x), primes(x).len() as u64);
+        }
+        assert_eq!(pi(1_000_000_000), 50847534);
+        assert_eq!(pi(10_000_000_000), 455052511);
+    }
+}
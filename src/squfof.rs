@@ -0,0 +1,156 @@
+use crate::driver;
+use crate::traits::{Factorizer, Factors, Splitter};
+use num::bigint::BigInt;
+use num::integer::{gcd, Roots};
+use num::ToPrimitive;
+
+// Largest input: P and Q stay below 2 sqrt(kn) < 2^38 for every multiplier.
+pub const MAX_BITS: u64 = 62;
+
+// Square-free products of 3, 5, 7 and 11, per Gower and Wagstaff.
+const MULTIPLIERS: [u64; 16] = [
+    1,
+    3,
+    5,
+    7,
+    11,
+    3 * 5,
+    3 * 7,
+    3 * 11,
+    5 * 7,
+    5 * 11,
+    7 * 11,
+    3 * 5 * 7,
+    3 * 5 * 11,
+    3 * 7 * 11,
+    5 * 7 * 11,
+    3 * 5 * 7 * 11,
+];
+
+pub struct Squfof {
+    n: BigInt,
+}
+
+impl Squfof {
+    pub fn new(n: BigInt) -> Self {
+        Self { n }
+    }
+}
+
+impl Factorizer for Squfof {
+    fn factorize(&self) -> Option<Factors> {
+        driver::factorize(&self.n, self)
+    }
+}
+
+impl Splitter for Squfof {
+    fn split(&self, n: &BigInt) -> Option<BigInt> {
+        split(n)
+    }
+}
+
+// Shanks' square forms factorization for n below 2^62, trying each multiplier
+// in turn. Returns None for larger n.
+pub fn split(n: &BigInt) -> Option<BigInt> {
+    if n.bits() > MAX_BITS {
+        return None;
+    }
+    let n = n.to_u64()?;
+    if n % 2 == 0 {
+        return Some(BigInt::from(2));
+    }
+    let r = n.sqrt();
+    if r * r == n {
+        return Some(BigInt::from(r));
+    }
+    MULTIPLIERS
+        .iter()
+        .find_map(|&k| squfof(n, k))
+        .map(BigInt::from)
+}
+
+// One multiplier: walk the principal cycle of forms of discriminant 4kn until
+// a proper square form turns up, then walk its square root's cycle to an
+// ambiguous form, which holds a factor of kn.
+fn squfof(n: u64, k: u64) -> Option<u64> {
+    let d = k as u128 * n as u128;
+    let p0 = d.sqrt() as u64;
+    // Square forms (r^2, P) whose root r was itself seen as Q / gcd(Q, 2k)
+    // below L = sqrt(2 sqrt(d)) are improper and lead nowhere; the queue keeps
+    // those values so they can be skipped.
+    let l = (2 * p0).sqrt();
+    let bound = 6 * l;
+    let mut queue: Vec<u64> = Vec::new();
+
+    let (mut p, mut q_prev) = (p0, 1u64);
+    let mut q = (d - p0 as u128 * p0 as u128) as u64;
+    let mut r = 0;
+    for i in 2..bound {
+        let b = (p0 + p) / q;
+        let p_next = b * q - p;
+        (q_prev, q) = (q, step(q_prev, b, p, p_next));
+        p = p_next;
+
+        if i % 2 == 0 {
+            let s = q.sqrt();
+            if s * s == q {
+                if s == 1 {
+                    // Back at the principal form: no proper square form.
+                    return None;
+                }
+                if !queue.contains(&s) {
+                    r = s;
+                    break;
+                }
+            }
+        }
+        let g = q / gcd(q, 2 * k);
+        if g <= l {
+            queue.push(g);
+        }
+    }
+    if r == 0 {
+        return None;
+    }
+
+    // Reverse cycle from the square root of the square form until P repeats.
+    let b = (p0 - p) / r;
+    let mut p = b * r + p;
+    let mut q_prev = r;
+    let mut q = ((d - p as u128 * p as u128) / r as u128) as u64;
+    for _ in 0..bound {
+        let b = (p0 + p) / q;
+        let p_next = b * q - p;
+        (q_prev, q) = (q, step(q_prev, b, p, p_next));
+        if p_next == p {
+            break;
+        }
+        p = p_next;
+    }
+    let f = gcd(n, q_prev);
+    (f != 1 && f != n).then_some(f)
+}
+
+// Q_(i+1) = Q_(i-1) + b (P_i - P_(i+1)), where the difference may be negative.
+fn step(q_prev: u64, b: u64, p: u64, p_next: u64) -> u64 {
+    (q_prev as i64 + b as i64 * (p as i64 - p_next as i64)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bi;
+
+    #[test]
+    fn test_squfof() {
+        let n = bi!("999962000357", 10);
+        let f = Squfof::new(n).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "999979 * 999983");
+
+        let n = bi!("4611685983281801657", 10);
+        let f = Squfof::new(n).factorize().unwrap();
+        assert_eq!(f.get_factors_expr(), "1000000007 * 4611685951");
+
+        assert_eq!(split(&bi!("36893488147419103231", 10)), None);
+    }
+}
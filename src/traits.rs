@@ -1,4 +1,5 @@
-use num::BigInt;
+use crate::algs::{primality, Primality};
+use num::{BigInt, One};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
@@ -8,23 +9,76 @@ pub trait Factorizer {
     fn factorize(&self) -> Option<Factors>;
 }
 
+// Splitter: one nontrivial divisor of a composite n, or None when the method
+// gives up. `driver::factorize` turns it into a full factorization.
+pub trait Splitter {
+    fn split(&self, n: &BigInt) -> Option<BigInt>;
+    // Whether the driver may finish a composite of at most 64 bits that
+    // `split` gave up on with the native engine, instead of reporting it.
+    fn fallback(&self) -> bool {
+        false
+    }
+}
+
+// Status of a factor: proven prime, probable prime by BPSW, composite, or
+// not tested yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+    Prime,
+    ProbablePrime,
+    Composite,
+    Unknown,
+}
+
+impl Status {
+    // Marks every factor but the proven primes in the text output formats.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Status::Prime => "",
+            Status::ProbablePrime => "P?",
+            Status::Composite => "C",
+            Status::Unknown => "?",
+        }
+    }
+}
+
+impl From<Primality> for Status {
+    fn from(primality: Primality) -> Self {
+        match primality {
+            Primality::Prime => Status::Prime,
+            Primality::ProbablePrime => Status::ProbablePrime,
+            Primality::Composite => Status::Composite,
+        }
+    }
+}
+
 // Factor
 #[derive(Debug, Clone, Eq)]
 pub struct Factor {
     p: BigInt,
     e: u32,
+    status: Status,
 }
 
 impl Factor {
+    #[cfg(test)]
     pub fn new(prime: BigInt, exp: u32) -> Self {
-        Self { p: prime, e: exp }
+        Self {
+            p: prime,
+            e: exp,
+            status: Status::Unknown,
+        }
     }
+    #[cfg(test)]
     pub fn q(&self) -> BigInt {
         let p = self.p.clone();
         p.pow(self.e)
     }
     pub fn pe(&self) -> (BigInt, u32) {
-        (self.p.clone(), self.e.clone())
+        (self.p.clone(), self.e)
+    }
+    pub fn status(&self) -> Status {
+        self.status
     }
 }
 impl Display for Factor {
@@ -43,7 +97,7 @@ impl Ord for Factor {
 }
 impl PartialOrd for Factor {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.p.cmp(&other.p))
+        Some(self.cmp(other))
     }
 }
 impl PartialEq for Factor {
@@ -55,39 +109,85 @@ impl PartialEq for Factor {
 // Factors
 #[derive(Debug, Clone)]
 pub struct Factors {
-    factors: HashMap<BigInt, u32>,
+    factors: HashMap<BigInt, (u32, Status)>,
 }
 impl Factors {
     pub fn new(factors: Option<HashMap<BigInt, u32>>) -> Self {
-        match factors {
-            Some(fs) => Self { factors: fs },
-            _ => Self {
-                factors: HashMap::new(),
-            },
+        let factors = factors.unwrap_or_default();
+        Self {
+            factors: factors
+                .into_iter()
+                .map(|(p, e)| (p, (e, Status::Unknown)))
+                .collect(),
         }
     }
+    #[cfg(test)]
     pub fn n(&self) -> BigInt {
-        self.factors.iter().map(|(p, e)| p.pow(*e)).product()
+        self.factors.iter().map(|(p, (e, _))| p.pow(*e)).product()
     }
 
     pub fn add(&mut self, p: BigInt) {
-        if self.factors.contains_key(&p) {
-            if let Some(e) = self.factors.get_mut(&p) {
-                *e += 1;
+        self.add_pow(p, 1);
+    }
+    pub fn add_pow(&mut self, p: BigInt, e: u32) {
+        self.insert(p, e, Status::Unknown);
+    }
+    // Adds p^e with a known status, which replaces an Unknown one.
+    pub fn insert(&mut self, p: BigInt, e: u32, status: Status) {
+        let entry = self.factors.entry(p).or_insert((0, Status::Unknown));
+        entry.0 += e;
+        if status != Status::Unknown {
+            entry.1 = status;
+        }
+    }
+    pub fn merge(&mut self, other: Factors) {
+        for (p, (e, status)) in other.factors {
+            self.insert(p, e, status);
+        }
+    }
+    pub fn status(&self, p: &BigInt) -> Option<Status> {
+        self.factors.get(p).map(|&(_, status)| status)
+    }
+    // Replaces the status of p, as when a certificate proves it prime.
+    pub fn set_status(&mut self, p: &BigInt, status: Status) {
+        if let Some(entry) = self.factors.get_mut(p) {
+            entry.1 = status;
+        }
+    }
+
+    // The product of the entries not known to be prime: 1 once the
+    // factorization is complete.
+    pub fn unfactored(&self) -> BigInt {
+        self.factors
+            .iter()
+            .filter(|(_, (_, status))| matches!(status, Status::Composite | Status::Unknown))
+            .map(|(p, (e, _))| p.pow(*e))
+            .product()
+    }
+    pub fn is_complete(&self) -> bool {
+        self.unfactored().is_one()
+    }
+    // Tests the entries whose status is still Unknown, so that each one is
+    // marked prime, probable prime or composite.
+    pub fn complete(&mut self) {
+        for (p, (_, status)) in self.factors.iter_mut() {
+            if *status == Status::Unknown {
+                *status = primality(p).into();
             }
-        } else {
-            self.factors.insert(p, 1);
         }
     }
+
+    #[cfg(test)]
     pub fn get_factors(&self) -> HashMap<BigInt, u32> {
-        self.factors.clone()
+        self.factors.iter().map(|(p, &(e, _))| (p.clone(), e)).collect()
     }
     pub fn get_factors_vector(&self) -> Vec<Factor> {
         let mut v = Vec::<Factor>::new();
-        for (p, e) in self.factors.iter() {
+        for (p, &(e, status)) in self.factors.iter() {
             v.push(Factor {
                 p: p.clone(),
-                e: e.clone(),
+                e,
+                status,
             })
         }
         v.sort();
@@ -95,24 +195,22 @@ impl Factors {
         v
     }
     pub fn get_factors_list(&self) -> String {
-        let mut factors: Vec<(&BigInt, &u32)> = Vec::from_iter(self.factors.iter());
-        factors.sort_by(|a, b| a.0.cmp(&b.0));
-        let factors_list: String = factors
+        let factors_list: String = self
+            .get_factors_vector()
             .iter()
-            .map(|(p, e)| format!("[{p}, {e}]"))
+            .map(|f| format!("[{}{}, {}]", f.status.prefix(), f.p, f.e))
             .collect::<Vec<_>>()
             .join(", ");
         format!("[{factors_list}]")
     }
 
     pub fn get_factors_flat_list(&self) -> String {
-        let mut factors: Vec<(&BigInt, &u32)> = Vec::from_iter(self.factors.iter());
-        factors.sort_by(|a, b| a.0.cmp(&b.0));
-        let factors_list: String = factors
+        let factors_list: String = self
+            .get_factors_vector()
             .iter()
-            .map(|(p, e)| {
-                let p_str = format!("{p}");
-                let pe: Vec<String> = vec![p_str; **e as usize];
+            .map(|f| {
+                let p_str = format!("{}{}", f.status.prefix(), f.p);
+                let pe: Vec<String> = vec![p_str; f.e as usize];
                 pe.join(", ")
             })
             .collect::<Vec<_>>()
@@ -121,15 +219,13 @@ impl Factors {
     }
 
     pub fn get_factors_expr(&self) -> String {
-        let mut factors: Vec<(&BigInt, &u32)> = Vec::from_iter(self.factors.iter());
-        factors.sort_by(|a, b| a.0.cmp(&b.0));
-        factors
+        self.get_factors_vector()
             .iter()
-            .map(|(p, e)| {
-                if **e == 1_u32 {
-                    format!("{p}")
+            .map(|f| {
+                if f.e == 1_u32 {
+                    format!("{}{}", f.status.prefix(), f.p)
                 } else {
-                    format!("{p}^{e}")
+                    format!("{}{}^{}", f.status.prefix(), f.p, f.e)
                 }
             })
             .collect::<Vec<_>>()
@@ -153,4 +249,39 @@ mod tests {
         let f = Factor::new(p, 5);
         assert_eq!(f.q(), BigInt::parse_bytes("242683778199500834259490681761793674473122071277452196998027316391426303843158647336337348864204968088977503838097970496463304346624".as_bytes(), 10).unwrap());
     }
+
+    #[test]
+    fn test_status() {
+        let mut factors = Factors::new(None);
+        factors.insert(BigInt::from(3), 2, Status::Prime);
+        factors.add(BigInt::from(1001));
+        factors.add(BigInt::from(13));
+        assert_eq!(factors.get_factors_expr(), "3^2 * ?13 * ?1001");
+        assert_eq!(factors.unfactored(), BigInt::from(13013));
+        assert!(!factors.is_complete());
+
+        factors.complete();
+        assert_eq!(factors.status(&BigInt::from(13)), Some(Status::Prime));
+        assert_eq!(factors.status(&BigInt::from(1001)), Some(Status::Composite));
+        assert_eq!(factors.status(&BigInt::from(7)), None);
+        assert_eq!(factors.get_factors_expr(), "3^2 * 13 * C1001");
+        assert_eq!(factors.get_factors_list(), "[[3, 2], [13, 1], [C1001, 1]]");
+        assert_eq!(factors.get_factors_flat_list(), "[3, 3, 13, C1001]");
+        assert_eq!(factors.unfactored(), BigInt::from(1001));
+
+        // A known status survives adding the same prime without one.
+        let mut other = Factors::new(None);
+        other.insert(BigInt::from(1001), 1, Status::Unknown);
+        other.insert(BigInt::from(7), 1, Status::Prime);
+        factors.merge(other);
+        assert_eq!(factors.get_factors_expr(), "3^2 * 7 * 13 * C1001^2");
+        assert_eq!(factors.n(), BigInt::from(9 * 7 * 13 * 1001 * 1001));
+
+        // Above 2^64, BPSW only makes a prime probable.
+        let p = BigInt::from(u64::MAX) + 14u32;
+        factors.add(p.clone());
+        factors.complete();
+        assert_eq!(factors.status(&p), Some(Status::ProbablePrime));
+        assert!(factors.get_factors_expr().ends_with(&format!(" * P?{p}")));
+    }
 }
@@ -1,10 +1,8 @@
-use crate::algs::{is_prime, is_square};
-use crate::traits::{Factor, Factorizer, Factors};
+use crate::algs::{primality, Primality};
+use crate::traits::{Factorizer, Factors, Status};
 use num::bigint::BigInt;
 use num::ToPrimitive;
 use num::{One, Zero};
-use std::collections::HashMap;
-use std::io::{stdout, Write};
 
 #[derive(Debug)]
 pub struct Trial {
@@ -23,23 +21,62 @@ impl Factorizer for Trial {
 }
 
 fn factorize(n: &BigInt) -> Option<Factors> {
+    if *n < BigInt::one() {
+        return None;
+    }
+    if let Some(n) = n.to_u64() {
+        return Some(factorize_u64(n));
+    }
     let mut n = n.clone();
-    let m = n.sqrt().to_u64().unwrap();
 
     let mut factors = Factors::new(None);
-    for i in 2..=m {
-        while n.clone() % i.clone() == BigInt::zero() {
-            factors.add(BigInt::from(i.clone()));
-            n /= i;
+    let mut status = primality(&n);
+    let mut i = 2u64;
+    while status == Primality::Composite && BigInt::from(i) * i <= n {
+        if (&n % i).is_zero() {
+            while (&n % i).is_zero() {
+                factors.insert(BigInt::from(i), 1, Status::Prime);
+                n /= i;
+            }
+            status = primality(&n);
         }
+        if i == u64::MAX {
+            break;
+        }
+        i += 1;
     }
+    // The division stops once the cofactor passes the primality test, or at
+    // u64::MAX, short of the square root of a cofactor above 2^128, which then
+    // stays composite.
     if n > BigInt::one() {
-        factors.add(BigInt::from(n.clone()));
+        let status = if BigInt::from(i) * i > n {
+            Status::Prime
+        } else {
+            status.into()
+        };
+        factors.insert(n, 1, status);
     }
 
     Some(factors)
 }
 
+// The same division loop on machine words.
+fn factorize_u64(mut n: u64) -> Factors {
+    let mut factors = Factors::new(None);
+    let mut i = 2;
+    while i <= n / i {
+        while n.is_multiple_of(i) {
+            factors.insert(BigInt::from(i), 1, Status::Prime);
+            n /= i;
+        }
+        i += 1;
+    }
+    if n > 1 {
+        factors.insert(BigInt::from(n), 1, Status::Prime);
+    }
+    factors
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,5 +88,11 @@ mod tests {
         let ff = Trial::new(n.clone());
         let f = ff.factorize().unwrap();
         assert_eq!(f.n(), n);
+
+        assert!(Trial::new(BigInt::from(-1)).factorize().is_none());
+        assert!(Trial::new(BigInt::zero()).factorize().is_none());
+        let n = (BigInt::one() << 130u32) - 8u32;
+        let f = Trial::new(n).factorize().unwrap();
+        assert_eq!(f.to_string(), "2^3 * P?170141183460469231731687303715884105727");
     }
 }